@@ -0,0 +1,404 @@
+//! Slot-aware cache of deserialized lookup tables
+
+use {
+    crate::{
+        error::AddressLookupError,
+        state::{AddressLookupTable, LookupTableStatus},
+    },
+    solana_program::{clock::Slot, pubkey::Pubkey},
+    std::{borrow::Cow, collections::HashMap},
+};
+
+/// Source of lookup table accounts, such as an RPC client
+pub trait LookupTableAccountSource {
+    type Error;
+
+    /// Fetch the owner and data of an account, or `None` if the account
+    /// doesn't exist
+    fn fetch_account(&mut self, address: &Pubkey)
+        -> Result<Option<(Pubkey, Vec<u8>)>, Self::Error>;
+}
+
+/// Errors returned by `LookupTableCache` lookups
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum LookupTableCacheError<E> {
+    /// The account source failed to fetch a lookup table account.
+    Source(E),
+    /// The lookup failed, in the same way as `AddressLookupTable::lookup`.
+    Lookup(AddressLookupError),
+}
+
+impl<E> From<AddressLookupError> for LookupTableCacheError<E> {
+    fn from(err: AddressLookupError) -> Self {
+        Self::Lookup(err)
+    }
+}
+
+#[derive(Debug)]
+struct CachedLookupTable {
+    table: AddressLookupTable<'static>,
+    /// The most recent slot at which the table was fetched or updated.
+    refreshed_slot: Slot,
+}
+
+/// Cache of deserialized lookup tables keyed by address
+///
+/// Tables are fetched from the account source the first time they're looked
+/// up. Entries are kept up to date by account updates, such as those from an
+/// account subscription, and are refetched at most once per slot when a
+/// lookup fails for an index which may have been appended since the table was
+/// last refreshed. Tables which are no longer active are evicted.
+#[derive(Debug)]
+pub struct LookupTableCache<S> {
+    source: S,
+    entries: HashMap<Pubkey, CachedLookupTable>,
+}
+
+impl<S: LookupTableAccountSource> LookupTableCache<S> {
+    pub fn new(source: S) -> Self {
+        Self {
+            source,
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Returns the cached table stored at an address
+    pub fn get(&self, address: &Pubkey) -> Option<&AddressLookupTable<'static>> {
+        self.entries.get(address).map(|entry| &entry.table)
+    }
+
+    /// Returns the number of cached tables
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether no tables are cached
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Apply an update of a lookup table account observed at `slot`, where
+    /// `account` is the account's owner and data, or `None` if the account
+    /// was closed. Entries are removed for closed or invalid accounts.
+    ///
+    /// Updates are ordered by slot, so updates observed at a slot older than
+    /// the one the cached table was last refreshed at are stale and ignored,
+    /// even if they close the account.
+    pub fn update_account(
+        &mut self,
+        address: Pubkey,
+        slot: Slot,
+        account: Option<(Pubkey, Vec<u8>)>,
+    ) -> Result<(), AddressLookupError> {
+        if self
+            .entries
+            .get(&address)
+            .is_some_and(|entry| slot < entry.refreshed_slot)
+        {
+            return Ok(());
+        }
+
+        let Some((owner, data)) = account else {
+            self.entries.remove(&address);
+            return Ok(());
+        };
+        if !crate::check_id(&owner) {
+            self.entries.remove(&address);
+            return Err(AddressLookupError::InvalidAccountOwner);
+        }
+        let Ok(table) = AddressLookupTable::deserialize(&data) else {
+            self.entries.remove(&address);
+            return Err(AddressLookupError::InvalidAccountData);
+        };
+        let table = AddressLookupTable {
+            meta: table.meta,
+            addresses: Cow::Owned(table.addresses.into_owned()),
+        };
+
+        self.entries.insert(
+            address,
+            CachedLookupTable {
+                table,
+                refreshed_slot: slot,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Lookup addresses for provided table indexes at `current_slot`,
+    /// fetching the table from the account source if needed
+    pub fn lookup(
+        &mut self,
+        address: &Pubkey,
+        current_slot: Slot,
+        indexes: &[u8],
+    ) -> Result<Vec<Pubkey>, LookupTableCacheError<S::Error>> {
+        if !self.entries.contains_key(address) {
+            self.refresh(address, current_slot)?;
+        }
+
+        let entry = self
+            .entries
+            .get(address)
+            .ok_or(AddressLookupError::LookupTableAccountNotFound)?;
+        // Permanently frozen and deactivated tables can't be extended, so
+        // only tables which were activated when they were last refreshed may
+        // have new addresses.
+        let may_be_extended = entry.refreshed_slot < current_slot
+            && entry.table.meta.authority.is_some()
+            && entry.table.meta.status(current_slot) == LookupTableStatus::Activated;
+
+        match entry.table.lookup(current_slot, indexes) {
+            Err(AddressLookupError::InvalidLookupIndex) if may_be_extended => {
+                self.refresh(address, current_slot)?;
+                self.lookup_cached(address, current_slot, indexes)
+            }
+            Err(AddressLookupError::LookupTableAccountNotFound) => {
+                self.entries.remove(address);
+                Err(AddressLookupError::LookupTableAccountNotFound.into())
+            }
+            result => result.map_err(LookupTableCacheError::from),
+        }
+    }
+
+    /// Remove cached tables which are no longer active at `current_slot`,
+    /// returning the number of removed tables
+    pub fn evict_inactive(&mut self, current_slot: Slot) -> usize {
+        let len = self.entries.len();
+        self.entries
+            .retain(|_, entry| entry.table.meta.is_active(current_slot));
+        len.saturating_sub(self.entries.len())
+    }
+
+    fn lookup_cached(
+        &mut self,
+        address: &Pubkey,
+        current_slot: Slot,
+        indexes: &[u8],
+    ) -> Result<Vec<Pubkey>, LookupTableCacheError<S::Error>> {
+        let entry = self
+            .entries
+            .get(address)
+            .ok_or(AddressLookupError::LookupTableAccountNotFound)?;
+        entry
+            .table
+            .lookup(current_slot, indexes)
+            .map_err(Into::into)
+    }
+
+    fn refresh(
+        &mut self,
+        address: &Pubkey,
+        current_slot: Slot,
+    ) -> Result<(), LookupTableCacheError<S::Error>> {
+        let account = self
+            .source
+            .fetch_account(address)
+            .map_err(LookupTableCacheError::Source)?;
+        self.update_account(*address, current_slot, account)
+            .map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::state::LookupTableMeta, solana_program::slot_hashes::MAX_ENTRIES};
+
+    /// In-memory account feed which counts fetches
+    #[derive(Default)]
+    struct TestAccountSource {
+        accounts: HashMap<Pubkey, (Pubkey, Vec<u8>)>,
+        fetches: usize,
+    }
+
+    impl TestAccountSource {
+        fn set_table(&mut self, address: Pubkey, meta: LookupTableMeta, addresses: &[Pubkey]) {
+            let data = AddressLookupTable {
+                meta,
+                addresses: Cow::Borrowed(addresses),
+            }
+            .serialize_for_tests()
+            .unwrap();
+            self.accounts.insert(address, (crate::id(), data));
+        }
+    }
+
+    impl LookupTableAccountSource for TestAccountSource {
+        type Error = &'static str;
+
+        fn fetch_account(
+            &mut self,
+            address: &Pubkey,
+        ) -> Result<Option<(Pubkey, Vec<u8>)>, Self::Error> {
+            self.fetches += 1;
+            Ok(self.accounts.get(address).cloned())
+        }
+    }
+
+    fn new_addresses(num_addresses: usize) -> Vec<Pubkey> {
+        let mut addresses = Vec::with_capacity(num_addresses);
+        addresses.resize_with(num_addresses, Pubkey::new_unique);
+        addresses
+    }
+
+    #[test]
+    fn test_lookup_fetches_missing_tables_once() {
+        let address = Pubkey::new_unique();
+        let addresses = new_addresses(3);
+        let mut source = TestAccountSource::default();
+        source.set_table(address, LookupTableMeta::default(), &addresses);
+        let mut cache = LookupTableCache::new(source);
+
+        assert_eq!(
+            cache.lookup(&address, 10, &[2, 0]),
+            Ok(vec![addresses[2], addresses[0]])
+        );
+        assert_eq!(cache.lookup(&address, 10, &[1]), Ok(vec![addresses[1]]));
+        assert_eq!(cache.source.fetches, 1);
+        assert_eq!(cache.len(), 1);
+
+        assert_eq!(
+            cache.lookup(&Pubkey::new_unique(), 10, &[0]),
+            Err(LookupTableCacheError::Lookup(
+                AddressLookupError::LookupTableAccountNotFound
+            ))
+        );
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_lookup_refetches_extended_tables() {
+        let address = Pubkey::new_unique();
+        let addresses = new_addresses(4);
+        let meta = LookupTableMeta::new(Pubkey::new_unique());
+        let mut cache = LookupTableCache::new(TestAccountSource::default());
+        cache
+            .source
+            .set_table(address, meta.clone(), &addresses[..2]);
+        assert_eq!(cache.lookup(&address, 10, &[1]), Ok(vec![addresses[1]]));
+
+        // The table is extended in slot 11
+        let extended_meta = LookupTableMeta {
+            last_extended_slot: 11,
+            last_extended_slot_start_index: 2,
+            ..meta
+        };
+        cache.source.set_table(address, extended_meta, &addresses);
+
+        // New addresses can't be looked up in the slot they were appended,
+        // and the table is only refetched once per slot
+        for _ in 0..2 {
+            assert_eq!(
+                cache.lookup(&address, 11, &[3]),
+                Err(LookupTableCacheError::Lookup(
+                    AddressLookupError::InvalidLookupIndex
+                ))
+            );
+        }
+        assert_eq!(cache.source.fetches, 2);
+
+        assert_eq!(cache.lookup(&address, 12, &[3]), Ok(vec![addresses[3]]));
+        assert_eq!(cache.source.fetches, 2);
+    }
+
+    #[test]
+    fn test_lookup_doesnt_refetch_frozen_tables() {
+        // Tables without an authority are permanently frozen
+        let address = Pubkey::new_unique();
+        let addresses = new_addresses(2);
+        let mut source = TestAccountSource::default();
+        source.set_table(address, LookupTableMeta::default(), &addresses);
+        let mut cache = LookupTableCache::new(source);
+
+        for current_slot in [10, 11] {
+            assert_eq!(
+                cache.lookup(&address, current_slot, &[u8::MAX]),
+                Err(LookupTableCacheError::Lookup(
+                    AddressLookupError::InvalidLookupIndex
+                ))
+            );
+        }
+        assert_eq!(cache.source.fetches, 1);
+    }
+
+    #[test]
+    fn test_update_account() {
+        let address = Pubkey::new_unique();
+        let addresses = new_addresses(3);
+        let mut source = TestAccountSource::default();
+        source.set_table(
+            address,
+            LookupTableMeta::new(Pubkey::new_unique()),
+            &addresses,
+        );
+        let account = source.accounts[&address].clone();
+        source.set_table(address, LookupTableMeta::default(), &addresses[..2]);
+        let stale_account = source.accounts[&address].clone();
+        let mut cache = LookupTableCache::new(source);
+
+        assert_eq!(cache.update_account(address, 10, Some(account)), Ok(()));
+        assert_eq!(cache.get(&address).unwrap().addresses.len(), 3);
+
+        // Updates from older slots are ignored, even if they close the account
+        assert_eq!(
+            cache.update_account(address, 9, Some(stale_account.clone())),
+            Ok(())
+        );
+        assert_eq!(cache.update_account(address, 9, None), Ok(()));
+        assert_eq!(cache.get(&address).unwrap().addresses.len(), 3);
+
+        // Updates from newer slots replace the cached table, such as when
+        // the table is frozen
+        assert_eq!(
+            cache.update_account(address, 11, Some(stale_account)),
+            Ok(())
+        );
+        assert_eq!(cache.get(&address).unwrap().meta.authority, None);
+
+        // Updates from other programs remove the entry
+        assert_eq!(
+            cache.update_account(address, 12, Some((Pubkey::new_unique(), vec![]))),
+            Err(AddressLookupError::InvalidAccountOwner)
+        );
+        assert!(cache.is_empty());
+
+        // Closed accounts remove the entry
+        let account = cache.source.accounts[&address].clone();
+        cache.update_account(address, 13, Some(account)).unwrap();
+        assert_eq!(cache.update_account(address, 14, None), Ok(()));
+        assert!(cache.is_empty());
+        assert_eq!(cache.source.fetches, 0);
+    }
+
+    #[test]
+    fn test_evict_deactivated_tables() {
+        let [deactivated, active] = [(); 2].map(|_| Pubkey::new_unique());
+        let addresses = new_addresses(1);
+        let mut source = TestAccountSource::default();
+        let deactivated_meta = LookupTableMeta {
+            deactivation_slot: 0,
+            ..LookupTableMeta::default()
+        };
+        source.set_table(deactivated, deactivated_meta, &addresses);
+        source.set_table(active, LookupTableMeta::default(), &addresses);
+        let mut cache = LookupTableCache::new(source);
+
+        assert_eq!(cache.lookup(&deactivated, 1, &[0]), Ok(addresses.clone()));
+        assert_eq!(cache.lookup(&active, 1, &[0]), Ok(addresses.clone()));
+
+        let current_slot = MAX_ENTRIES as Slot;
+        assert_eq!(cache.evict_inactive(current_slot), 1);
+        assert!(cache.get(&deactivated).is_none());
+
+        // Lookups from tables which are no longer active evict them
+        assert_eq!(
+            cache.lookup(&deactivated, current_slot, &[0]),
+            Err(LookupTableCacheError::Lookup(
+                AddressLookupError::LookupTableAccountNotFound
+            ))
+        );
+        assert!(cache.get(&deactivated).is_none());
+        assert_eq!(cache.len(), 1);
+    }
+}
@@ -0,0 +1,76 @@
+//! Off-chain helpers for lookup table clients
+
+use {
+    crate::state::LookupTableMeta,
+    solana_program::clock::Slot,
+    std::{thread, time::Duration},
+};
+
+/// Blocks until `get_slot` reports a slot at which the lookup table can be
+/// closed, polling every `poll_interval`, and returns the reported slot.
+/// Returns `None` without polling if the table hasn't been deactivated.
+///
+/// The program checks deactivation against the `Clock` slot of the bank
+/// processing the close instruction, so `get_slot` should report slots at
+/// the commitment level the close transaction is sent with.
+pub fn wait_until_closable<F, E>(
+    lookup_table_meta: &LookupTableMeta,
+    poll_interval: Duration,
+    mut get_slot: F,
+) -> Result<Option<Slot>, E>
+where
+    F: FnMut() -> Result<Slot, E>,
+{
+    let Some(closable_slot) = lookup_table_meta.closable_at_slot() else {
+        return Ok(None);
+    };
+
+    loop {
+        let slot = get_slot()?;
+        if slot >= closable_slot {
+            return Ok(Some(slot));
+        }
+        thread::sleep(poll_interval);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, solana_program::slot_hashes::MAX_ENTRIES};
+
+    #[test]
+    fn test_wait_until_closable() {
+        let meta = LookupTableMeta {
+            deactivation_slot: 10,
+            ..LookupTableMeta::default()
+        };
+        let closable_slot = 10 + MAX_ENTRIES as Slot;
+
+        let mut slots = (closable_slot - 3..).step_by(2);
+        let mut polls = 0;
+        let result: Result<_, ()> = wait_until_closable(&meta, Duration::ZERO, || {
+            polls += 1;
+            Ok(slots.next().unwrap())
+        });
+        assert_eq!(result, Ok(Some(closable_slot + 1)));
+        assert_eq!(polls, 3);
+    }
+
+    #[test]
+    fn test_wait_until_closable_without_deactivation() {
+        let result = wait_until_closable(&LookupTableMeta::default(), Duration::ZERO, || {
+            Err::<Slot, _>("should not poll")
+        });
+        assert_eq!(result, Ok(None));
+    }
+
+    #[test]
+    fn test_wait_until_closable_with_error() {
+        let meta = LookupTableMeta {
+            deactivation_slot: 10,
+            ..LookupTableMeta::default()
+        };
+        let result = wait_until_closable(&meta, Duration::ZERO, || Err::<Slot, _>("rpc error"));
+        assert_eq!(result, Err("rpc error"));
+    }
+}
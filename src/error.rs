@@ -2,7 +2,54 @@
 
 #[cfg(not(target_os = "solana"))]
 use solana_program::message::AddressLoaderError;
-use spl_program_error::*;
+use {solana_program::program_error::ProgramError, spl_program_error::*};
+
+/// Instructions which process multiple lookup tables fail with a custom
+/// error code of this offset, plus the index of the table which failed
+/// shifted by `LOOKUP_TABLES_ERROR_INDEX_SHIFT`, plus a code for the table's
+/// own error.
+pub const LOOKUP_TABLES_ERROR_OFFSET: u32 = 1 << 24;
+
+/// Shift of the failed table's index in the custom error code of an
+/// instruction which processes multiple lookup tables
+pub const LOOKUP_TABLES_ERROR_INDEX_SHIFT: u32 = 16;
+
+/// Flag set in the cause code of a failed table's error for builtin program
+/// errors. Otherwise the cause code is the table's custom error code.
+const BUILTIN_CAUSE_FLAG: u32 = 1 << 15;
+const CAUSE_MASK: u32 = (1 << LOOKUP_TABLES_ERROR_INDEX_SHIFT) - 1;
+
+/// Returns the error of an instruction which processes multiple lookup
+/// tables when the table at `index` fails with `error`. Custom errors which
+/// don't fit in the cause code are reported as `ProgramError::Custom` with
+/// the largest custom cause code.
+pub fn lookup_tables_error(index: u8, error: ProgramError) -> ProgramError {
+    let cause = match error {
+        ProgramError::Custom(code) => code.min(BUILTIN_CAUSE_FLAG - 1),
+        builtin => {
+            let builtin_index = u64::from(builtin) >> 32;
+            BUILTIN_CAUSE_FLAG | (builtin_index as u32 & (BUILTIN_CAUSE_FLAG - 1))
+        }
+    };
+    ProgramError::Custom(
+        LOOKUP_TABLES_ERROR_OFFSET | (u32::from(index) << LOOKUP_TABLES_ERROR_INDEX_SHIFT) | cause,
+    )
+}
+
+/// Returns the index of the table which failed an instruction processing
+/// multiple lookup tables and the table's own error, from the instruction's
+/// custom error code
+pub fn failed_lookup_table_error(error_code: u32) -> Option<(u8, ProgramError)> {
+    let encoded = error_code.checked_sub(LOOKUP_TABLES_ERROR_OFFSET)?;
+    let index = u8::try_from(encoded >> LOOKUP_TABLES_ERROR_INDEX_SHIFT).ok()?;
+    let cause = encoded & CAUSE_MASK;
+    let error = if cause & BUILTIN_CAUSE_FLAG != 0 {
+        ProgramError::from(u64::from(cause & !BUILTIN_CAUSE_FLAG) << 32)
+    } else {
+        ProgramError::Custom(cause)
+    };
+    Some((index, error))
+}
 
 #[spl_program_error]
 pub enum AddressLookupError {
@@ -18,6 +65,21 @@ pub enum AddressLookupError {
     /// Address lookup contains an invalid index
     #[error("Address lookup contains an invalid index")]
     InvalidLookupIndex,
+    /// Lookup table length does not match the expected extend start index
+    #[error("Lookup table length does not match the expected extend start index")]
+    StartIndexMismatch,
+    /// Lookup table addresses do not match the expected addresses
+    #[error("Lookup table addresses do not match the expected addresses")]
+    AddressesMismatch,
+    /// Append-only lookup tables cannot be deactivated or closed
+    #[error("Append-only lookup tables cannot be deactivated or closed")]
+    AppendOnly,
+    /// Lookup table successor chain is invalid
+    #[error("Lookup table successor chain is invalid")]
+    InvalidSuccessor,
+    /// Lookup table already records the maximum number of funders
+    #[error("Lookup table already records the maximum number of funders")]
+    TooManyFunders,
 }
 
 #[cfg(not(target_os = "solana"))]
@@ -28,6 +90,45 @@ impl From<AddressLookupError> for AddressLoaderError {
             AddressLookupError::InvalidAccountOwner => Self::InvalidAccountOwner,
             AddressLookupError::InvalidAccountData => Self::InvalidAccountData,
             AddressLookupError::InvalidLookupIndex => Self::InvalidLookupIndex,
+            // Instruction processing errors are never encountered while
+            // loading addresses.
+            AddressLookupError::StartIndexMismatch
+            | AddressLookupError::AddressesMismatch
+            | AddressLookupError::AppendOnly
+            | AddressLookupError::TooManyFunders => Self::InvalidAccountData,
+            // Successors are only followed by off-chain resolvers.
+            AddressLookupError::InvalidSuccessor => Self::InvalidAccountData,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup_tables_error() {
+        for (index, error) in [
+            (0, ProgramError::Custom(0)),
+            (1, AddressLookupError::AppendOnly.into()),
+            (7, ProgramError::InvalidArgument),
+            (u8::MAX, ProgramError::MissingRequiredSignature),
+            (3, ProgramError::InvalidAccountOwner),
+        ] {
+            let ProgramError::Custom(error_code) = lookup_tables_error(index, error.clone()) else {
+                panic!("Expected a custom error");
+            };
+            assert_eq!(failed_lookup_table_error(error_code), Some((index, error)));
+        }
+
+        assert_eq!(
+            lookup_tables_error(2, ProgramError::Custom(u32::MAX)),
+            lookup_tables_error(2, ProgramError::Custom(BUILTIN_CAUSE_FLAG - 1))
+        );
+        assert_eq!(
+            failed_lookup_table_error(LOOKUP_TABLES_ERROR_OFFSET - 1),
+            None
+        );
+        assert_eq!(failed_lookup_table_error(u32::MAX), None);
+    }
+}
@@ -0,0 +1,212 @@
+//! Account data filters for querying lookup tables by their metadata
+//!
+//! Offsets are relative to the start of a lookup table account's data, which
+//! begins with the `ProgramState` enum tag followed by the serialized
+//! `LookupTableMeta`. Each offset follows from the bincode-serialized sizes
+//! of the fields before it.
+
+use {
+    solana_program::{clock::Slot, pubkey::Pubkey},
+    std::mem::size_of,
+};
+
+/// Offset of the `ProgramState` enum tag, serialized as a little-endian `u32`
+pub const PROGRAM_STATE_TAG_OFFSET: usize = 0;
+
+/// Offset of `LookupTableMeta::deactivation_slot`
+pub const DEACTIVATION_SLOT_OFFSET: usize = PROGRAM_STATE_TAG_OFFSET + size_of::<u32>();
+
+/// Offset of `LookupTableMeta::last_extended_slot`
+pub const LAST_EXTENDED_SLOT_OFFSET: usize = DEACTIVATION_SLOT_OFFSET + size_of::<Slot>();
+
+/// Offset of `LookupTableMeta::last_extended_slot_start_index`
+pub const LAST_EXTENDED_SLOT_START_INDEX_OFFSET: usize =
+    LAST_EXTENDED_SLOT_OFFSET + size_of::<Slot>();
+
+/// Offset of the `Option` tag of `LookupTableMeta::authority`, which is `0`
+/// for frozen tables and `1` otherwise
+pub const AUTHORITY_OPTION_OFFSET: usize = LAST_EXTENDED_SLOT_START_INDEX_OFFSET + size_of::<u8>();
+
+/// Offset of the `LookupTableMeta::authority` address, if the table has one
+pub const AUTHORITY_OFFSET: usize = AUTHORITY_OPTION_OFFSET + size_of::<u8>();
+
+/// `ProgramState` enum tag of lookup table accounts
+pub const LOOKUP_TABLE_TAG: u32 = 1;
+
+/// Matches accounts whose data contains `bytes` starting at `offset`, as
+/// with the `memcmp` filter of the `getProgramAccounts` RPC method.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MemcmpFilter {
+    pub offset: usize,
+    pub bytes: Vec<u8>,
+}
+
+impl MemcmpFilter {
+    pub fn new(offset: usize, bytes: Vec<u8>) -> Self {
+        Self { offset, bytes }
+    }
+
+    /// Returns whether account data matches the filter
+    pub fn matches(&self, data: &[u8]) -> bool {
+        self.offset
+            .checked_add(self.bytes.len())
+            .and_then(|end| data.get(self.offset..end))
+            .is_some_and(|bytes| bytes == self.bytes.as_slice())
+    }
+}
+
+/// Matches lookup table accounts. Companion accounts owned by the program
+/// never match. Combine with the other filters to avoid matching companion
+/// accounts whose data happens to match at the same offsets.
+pub fn lookup_table_filter() -> MemcmpFilter {
+    MemcmpFilter::new(
+        PROGRAM_STATE_TAG_OFFSET,
+        LOOKUP_TABLE_TAG.to_le_bytes().to_vec(),
+    )
+}
+
+/// Matches lookup tables controlled by `authority`
+pub fn authority_filter(authority: &Pubkey) -> MemcmpFilter {
+    let mut bytes = vec![1];
+    bytes.extend_from_slice(authority.as_ref());
+    MemcmpFilter::new(AUTHORITY_OPTION_OFFSET, bytes)
+}
+
+/// Matches permanently frozen lookup tables
+pub fn frozen_filter() -> MemcmpFilter {
+    MemcmpFilter::new(AUTHORITY_OPTION_OFFSET, vec![0])
+}
+
+/// Matches lookup tables with the given deactivation slot
+pub fn deactivation_slot_filter(deactivation_slot: Slot) -> MemcmpFilter {
+    MemcmpFilter::new(
+        DEACTIVATION_SLOT_OFFSET,
+        deactivation_slot.to_le_bytes().to_vec(),
+    )
+}
+
+/// Matches lookup tables which haven't been deactivated, including tables
+/// whose deactivation is only scheduled
+pub fn not_deactivated_filter() -> MemcmpFilter {
+    deactivation_slot_filter(Slot::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::state::{AddressLookupTable, LookupTableMeta, ProgramState, LOOKUP_TABLE_META_SIZE},
+        std::borrow::Cow,
+    };
+
+    fn serialize_table(meta: LookupTableMeta) -> Vec<u8> {
+        AddressLookupTable {
+            meta,
+            addresses: Cow::Owned(vec![Pubkey::new_unique()]),
+        }
+        .serialize_for_tests()
+        .unwrap()
+    }
+
+    #[test]
+    fn test_offsets_match_bincode() {
+        let meta = LookupTableMeta {
+            deactivation_slot: 1,
+            last_extended_slot: 2,
+            last_extended_slot_start_index: 3,
+            authority: Some(Pubkey::new_unique()),
+            _padding: 4,
+        };
+        let data = bincode::serialize(&ProgramState::LookupTable(meta.clone())).unwrap();
+
+        let field_offsets = [
+            (
+                PROGRAM_STATE_TAG_OFFSET,
+                bincode::serialize(&LOOKUP_TABLE_TAG).unwrap(),
+            ),
+            (
+                DEACTIVATION_SLOT_OFFSET,
+                bincode::serialize(&meta.deactivation_slot).unwrap(),
+            ),
+            (
+                LAST_EXTENDED_SLOT_OFFSET,
+                bincode::serialize(&meta.last_extended_slot).unwrap(),
+            ),
+            (
+                LAST_EXTENDED_SLOT_START_INDEX_OFFSET,
+                bincode::serialize(&meta.last_extended_slot_start_index).unwrap(),
+            ),
+            (
+                AUTHORITY_OPTION_OFFSET,
+                bincode::serialize(&meta.authority).unwrap(),
+            ),
+        ];
+        for (offset, bytes) in field_offsets {
+            assert_eq!(data[offset..offset + bytes.len()], bytes);
+        }
+        assert_eq!(
+            data[AUTHORITY_OFFSET..AUTHORITY_OFFSET + 32],
+            bincode::serialize(&meta.authority.unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_offsets_match_new_lookup_table() {
+        let authority = Pubkey::new_unique();
+        let mut data = vec![0; LOOKUP_TABLE_META_SIZE];
+        ProgramState::serialize_new_lookup_table(&mut data, &authority).unwrap();
+
+        assert_eq!(
+            data[PROGRAM_STATE_TAG_OFFSET..DEACTIVATION_SLOT_OFFSET],
+            LOOKUP_TABLE_TAG.to_le_bytes()
+        );
+        assert_eq!(
+            data[DEACTIVATION_SLOT_OFFSET..LAST_EXTENDED_SLOT_OFFSET],
+            Slot::MAX.to_le_bytes()
+        );
+        assert_eq!(
+            data[LAST_EXTENDED_SLOT_OFFSET..LAST_EXTENDED_SLOT_START_INDEX_OFFSET],
+            0u64.to_le_bytes()
+        );
+        assert_eq!(data[LAST_EXTENDED_SLOT_START_INDEX_OFFSET], 0);
+        assert_eq!(data[AUTHORITY_OPTION_OFFSET], 1);
+        assert_eq!(
+            data[AUTHORITY_OFFSET..AUTHORITY_OFFSET + 32],
+            authority.to_bytes()
+        );
+
+        assert!(lookup_table_filter().matches(&data));
+        assert!(authority_filter(&authority).matches(&data));
+        assert!(!authority_filter(&Pubkey::new_unique()).matches(&data));
+        assert!(!frozen_filter().matches(&data));
+        assert!(not_deactivated_filter().matches(&data));
+    }
+
+    #[test]
+    fn test_offsets_match_lookup_table_meta() {
+        let meta = LookupTableMeta {
+            deactivation_slot: 1,
+            last_extended_slot: 2,
+            last_extended_slot_start_index: 3,
+            authority: None,
+            ..LookupTableMeta::default()
+        };
+        let data = serialize_table(meta);
+
+        assert_eq!(
+            data[LAST_EXTENDED_SLOT_OFFSET..LAST_EXTENDED_SLOT_START_INDEX_OFFSET],
+            2u64.to_le_bytes()
+        );
+        assert_eq!(data[LAST_EXTENDED_SLOT_START_INDEX_OFFSET], 3);
+        assert!(frozen_filter().matches(&data));
+        assert!(deactivation_slot_filter(1).matches(&data));
+        assert!(!not_deactivated_filter().matches(&data));
+    }
+
+    #[test]
+    fn test_memcmp_filter_out_of_bounds() {
+        let filter = MemcmpFilter::new(usize::MAX, vec![0]);
+        assert!(!filter.matches(&[0; 4]));
+        assert!(!authority_filter(&Pubkey::new_unique()).matches(&[1; 8]));
+    }
+}
@@ -1,19 +1,54 @@
 //! Program instruction types
 
 use {
+    crate::state::{
+        AddressLookupTable, LookupTableExtendMode, LOOKUP_TABLE_FUNDERS_SEED,
+        LOOKUP_TABLE_LABELS_SEED, LOOKUP_TABLE_REGISTRY_SEED, LOOKUP_TABLE_SETTINGS_SEED,
+    },
     serde::{Deserialize, Serialize},
     solana_program::{
         clock::Slot,
+        hash::Hash,
         instruction::{AccountMeta, Instruction},
         pubkey::Pubkey,
         system_program,
     },
 };
 
+/// Behavior of lookup table creation when the table account already exists
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CreateLookupTableMode {
+    /// Fail if the table account already exists.
+    Strict,
+    /// Succeed without changes if the table account already exists and is
+    /// controlled by the same authority. The outcome is reported through
+    /// return data as a `CreateLookupTableOutcome`.
+    Idempotent,
+}
+
+/// Outcome of an idempotent lookup table creation
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CreateLookupTableOutcome {
+    /// The table account was created.
+    Created,
+    /// The table account already existed.
+    AlreadyExists,
+}
+
+/// Instructions of the address lookup table program
+///
+/// Instructions signed by the table authority require the settings account
+/// of tables with a settings account, since it records the slot until which
+/// the table is frozen. The settings account can be passed at any index
+/// after the documented account references.
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
 pub enum ProgramInstruction {
     /// Create an address lookup table
     ///
+    /// If the authority's registry account is provided, the new table is
+    /// added to the registry. This applies to every instruction which creates
+    /// a lookup table.
+    ///
     /// # Account references
     ///   0. `[WRITE]` Uninitialized address lookup table account
     ///   1. `[SIGNER]` Account used to derive and control the new address
@@ -21,6 +56,7 @@ pub enum ProgramInstruction {
     ///   2. `[SIGNER, WRITE]` Account that will fund the new address lookup
     ///      table.
     ///   3. `[]` System program for CPI.
+    ///   4. `[WRITE, OPTIONAL]` Registry account of the authority
     CreateLookupTable {
         /// A recent slot must be used in the derivation path
         /// for each initialized table. When closing table accounts,
@@ -35,10 +71,14 @@ pub enum ProgramInstruction {
     },
 
     /// Permanently freeze an address lookup table, making it immutable.
+    /// Frozen tables no longer have an authority, so tables listed in the
+    /// authority's registry are removed from it.
     ///
     /// # Account references
     ///   0. `[WRITE]` Address lookup table account to freeze
     ///   1. `[SIGNER]` Current authority
+    ///   2. `[WRITE, OPTIONAL]` Registry account of the authority, if the
+    ///      table is registered
     FreezeLookupTable,
 
     /// Extend an address lookup table with new addresses. Funding account and
@@ -46,6 +86,16 @@ pub enum ProgramInstruction {
     /// account requires additional lamports to cover the rent-exempt balance
     /// after being extended.
     ///
+    /// Tables with an open `LookupTableExtendMode` can be extended by any
+    /// signer in place of the current authority. Tables with a settings
+    /// account require it after the optional account references. Tables with
+    /// an extend hook also require the hook program account, and any accounts
+    /// the hook program requires, after the optional account references.
+    /// Tables which record their funders also require the funders account
+    /// after the optional account references whenever the payer funds an
+    /// extension signed by the current authority. Extensions signed by other
+    /// signers aren't recorded.
+    ///
     /// # Account references
     ///   0. `[WRITE]` Address lookup table account to extend
     ///   1. `[SIGNER]` Current authority
@@ -64,11 +114,569 @@ pub enum ProgramInstruction {
 
     /// Close an address lookup table account
     ///
+    /// Tables with a settings account also close their settings account,
+    /// unless it records a successor, in which case it's kept so that the
+    /// successor can still be resolved from the closed table's address.
+    /// Tables which record their funders also close their funders account.
+    /// Each recorded funder is refunded its contribution, or a pro rata share
+    /// if the table's lamports don't cover every contribution, and the
+    /// recipient receives the rest. Refunds of funders whose accounts are
+    /// missing, or wouldn't be rent exempt after the refund, are sent to the
+    /// recipient instead. Tables listed in their authority's
+    /// registry are removed from it, which requires the registry account
+    /// after the other account references.
+    ///
     /// # Account references
     ///   0. `[WRITE]` Address lookup table account to close
     ///   1. `[SIGNER]` Current authority
     ///   2. `[WRITE]` Recipient of closed account lamports
+    ///   3. `[WRITE, OPTIONAL]` Funders account, if the table records its
+    ///      funders
+    ///   4. ..`4+N` `[WRITE, OPTIONAL]` Accounts of each recorded funder
+    ///
+    /// The settings account of tables with a settings account can be passed
+    /// at any index after the recipient.
     CloseLookupTable,
+
+    /// Create an address lookup table which is initialized with addresses.
+    /// The table account is allocated and funded for its final size up front.
+    /// Fails if the table account already exists.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Uninitialized address lookup table account
+    ///   1. `[]` Account used to derive and control the new address lookup
+    ///      table.
+    ///   2. `[SIGNER, WRITE]` Account that will fund the new address lookup
+    ///      table.
+    ///   3. `[]` System program for CPI.
+    ///   4. `[WRITE, OPTIONAL]` Registry account of the authority
+    CreateLookupTableWithAddresses {
+        /// A recent slot used in the derivation path. See
+        /// `ProgramInstruction::CreateLookupTable`.
+        recent_slot: Slot,
+        /// The bump seed of the derived table address.
+        bump_seed: u8,
+        /// Addresses the new table is initialized with.
+        addresses: Vec<Pubkey>,
+    },
+
+    /// Reserve capacity in an address lookup table account by funding the
+    /// rent-exempt balance of a table with `capacity` addresses up front, so
+    /// that extensions don't need a funding account as long as the new
+    /// addresses fit. The account isn't reallocated, since the runtime would
+    /// resolve any unfilled address slots. Reserved lamports can be withdrawn
+    /// with `WithdrawExcessLamports`.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Address lookup table account to reserve capacity in
+    ///   1. `[SIGNER]` Current authority
+    ///   2. `[SIGNER, WRITE]` Account that will fund the reserved capacity
+    ///   3. `[]` System program for CPI.
+    ReserveLookupTableCapacity {
+        /// The total number of addresses the table should have room for.
+        capacity: u16,
+    },
+
+    /// Withdraw lamports held by an address lookup table account in excess
+    /// of its rent-exempt balance. Tables may be withdrawn from while active.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Address lookup table account to withdraw from
+    ///   1. `[SIGNER]` Current authority
+    ///   2. `[WRITE]` Recipient of withdrawn lamports
+    WithdrawExcessLamports,
+
+    /// Create an address lookup table, with explicit behavior for when the
+    /// table account already exists. Unlike `CreateLookupTable`, which
+    /// silently succeeds for existing tables, this can either fail or verify
+    /// that the existing table is controlled by the same authority.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Uninitialized address lookup table account
+    ///   1. `[]` Account used to derive and control the new address lookup
+    ///      table.
+    ///   2. `[SIGNER, WRITE]` Account that will fund the new address lookup
+    ///      table.
+    ///   3. `[]` System program for CPI.
+    ///   4. `[WRITE, OPTIONAL]` Registry account of the authority
+    CreateLookupTableChecked {
+        /// A recent slot used in the derivation path. See
+        /// `ProgramInstruction::CreateLookupTable`.
+        recent_slot: Slot,
+        /// The bump seed of the derived table address.
+        bump_seed: u8,
+        /// Behavior when the table account already exists.
+        mode: CreateLookupTableMode,
+    },
+
+    /// Extend an address lookup table with new addresses, only if the new
+    /// addresses will be stored starting at the expected index. Funding
+    /// account and system program account references are only required if
+    /// the lookup table account requires additional lamports to cover the
+    /// rent-exempt balance after being extended.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Address lookup table account to extend
+    ///   1. `[SIGNER]` Current authority
+    ///   2. `[SIGNER, WRITE, OPTIONAL]` Account that will fund the table
+    ///      reallocation
+    ///   3. `[OPTIONAL]` System program for CPI.
+    ExtendLookupTableChecked {
+        new_addresses: Vec<Pubkey>,
+        /// The number of addresses the table must contain before it's
+        /// extended, which is the index of the first new address.
+        expected_start_index: u8,
+    },
+
+    /// Permanently freeze an address lookup table, making it immutable, only
+    /// if its addresses match the expected addresses.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Address lookup table account to freeze
+    ///   1. `[SIGNER]` Current authority
+    ///   2. `[WRITE, OPTIONAL]` Registry account of the authority, if the
+    ///      table is registered
+    FreezeLookupTableChecked {
+        /// The expected number of addresses in the table.
+        expected_addresses_len: u16,
+        /// The expected hash of the table's addresses, as computed by
+        /// `AddressLookupTable::hash_addresses`.
+        expected_addresses_hash: Hash,
+    },
+
+    /// Extend an address lookup table with new addresses, so that retrying
+    /// the same extension is safe. If the new addresses are already stored
+    /// at the end of the table starting from the expected index, this is a
+    /// no-op. Otherwise, the table is extended as with
+    /// `ExtendLookupTableChecked`. Funding account and system program account
+    /// references are only required if the lookup table account requires
+    /// additional lamports to cover the rent-exempt balance after being
+    /// extended.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Address lookup table account to extend
+    ///   1. `[SIGNER]` Current authority
+    ///   2. `[SIGNER, WRITE, OPTIONAL]` Account that will fund the table
+    ///      reallocation
+    ///   3. `[OPTIONAL]` System program for CPI.
+    ExtendLookupTableIdempotent {
+        new_addresses: Vec<Pubkey>,
+        /// The index of the first new address in the extended table.
+        expected_start_index: u8,
+    },
+
+    /// Extend an address lookup table with the addresses of instruction
+    /// accounts, rather than addresses in instruction data. Since these
+    /// accounts may be loaded from other lookup tables, many more addresses
+    /// fit in a single transaction. Funding account and system program
+    /// account references are always required, so that the appended accounts
+    /// start at a fixed index.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Address lookup table account to extend
+    ///   1. `[SIGNER]` Current authority
+    ///   2. `[SIGNER, WRITE]` Account that will fund the table reallocation
+    ///   3. `[]` System program for CPI.
+    ///   4. ..`4+N` `[]` Accounts whose addresses will be appended, where `N`
+    ///      is `num_addresses`. Accounts after these, like the funders account
+    ///      and extend hook program account, aren't appended.
+    ExtendFromAccounts {
+        /// The number of accounts whose addresses will be appended.
+        num_addresses: u16,
+    },
+
+    /// Extend an address lookup table with a range of addresses copied from
+    /// another address lookup table. Funding account and system program
+    /// account references are only required if the lookup table account
+    /// requires additional lamports to cover the rent-exempt balance after
+    /// being extended.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Address lookup table account to extend
+    ///   1. `[SIGNER]` Current authority
+    ///   2. `[]` Address lookup table account to copy addresses from
+    ///   3. `[SIGNER, WRITE, OPTIONAL]` Account that will fund the table
+    ///      reallocation
+    ///   4. `[OPTIONAL]` System program for CPI.
+    CopyFromLookupTable {
+        /// The index of the first address to copy from the source table.
+        start_index: u8,
+        /// The number of addresses to copy from the source table.
+        num_addresses: u16,
+    },
+
+    /// Deactivate multiple address lookup tables sharing the same authority,
+    /// applying the same checks as `DeactivateLookupTable` to each table.
+    /// Fails with `error::lookup_tables_error` for the first table which
+    /// can't be deactivated, which encodes both the table's index and its
+    /// error.
+    ///
+    /// # Account references
+    ///   0. `[SIGNER]` Current authority
+    ///   1. ..`1+N` `[WRITE]` Address lookup table accounts to deactivate,
+    ///      where `N` is `num_lookup_tables`. Accounts after these are
+    ///      passed along with each table.
+    DeactivateLookupTables {
+        /// The number of lookup table accounts to deactivate.
+        num_lookup_tables: u8,
+    },
+
+    /// Close multiple address lookup tables sharing the same authority,
+    /// applying the same checks as `CloseLookupTable` to each table. Fails
+    /// with `error::lookup_tables_error` for the first table which can't be
+    /// closed, which encodes both the table's index and its error.
+    ///
+    /// # Account references
+    ///   0. `[SIGNER]` Current authority
+    ///   1. `[WRITE]` Recipient of reclaimed lamports
+    ///   2. ..`2+N` `[WRITE]` Address lookup table accounts to close, where
+    ///      `N` is `num_lookup_tables`. Accounts after these, like the funders
+    ///      and registry accounts of the tables, are passed along with each
+    ///      table.
+    CloseLookupTables {
+        /// The number of lookup table accounts to close.
+        num_lookup_tables: u8,
+    },
+
+    /// Schedule an address lookup table to be deactivated at a slot. The
+    /// slot is stored in the table's settings account and the table remains
+    /// activated until `DeactivateScheduledLookupTable` is processed once the
+    /// slot is reached. The deactivation may be rescheduled until then. A
+    /// deactivation slot equal to the current slot deactivates the table
+    /// immediately. Funding account and system program account references are
+    /// only required if the settings account doesn't exist yet.
+    ///
+    /// The table's `deactivation_slot` isn't set to the future slot, and
+    /// `LookupTableMeta::status` doesn't treat future deactivation slots as
+    /// activated. The runtime reads `deactivation_slot` directly and treats
+    /// any slot other than `Slot::MAX` which isn't a recent slot as
+    /// deactivated, so the table would become unusable immediately.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Address lookup table account to deactivate
+    ///   1. `[SIGNER]` Current authority
+    ///   2. `[WRITE]` Settings account, derived from the address lookup table
+    ///      address
+    ///   3. `[SIGNER, WRITE, OPTIONAL]` Account that will fund the settings
+    ///      account
+    ///   4. `[OPTIONAL]` System program for CPI.
+    DeactivateLookupTableAtSlot {
+        /// The slot at which the table will be deactivated. Must not be
+        /// before the current slot.
+        deactivation_slot: Slot,
+    },
+
+    /// Create an address lookup table which can be deactivated by
+    /// `DeactivateScheduledLookupTable` once the deactivation slot is
+    /// reached. The deactivation slot is stored in the table's settings
+    /// account, which is created as well. Fails if the table already exists.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Uninitialized address lookup table account
+    ///   1. `[]` Account used to derive and control the new address lookup table.
+    ///   2. `[SIGNER, WRITE]` Account that will fund the new address lookup
+    ///      table and its settings account.
+    ///   3. `[]` System program for CPI.
+    ///   4. `[WRITE]` Uninitialized settings account, derived from the address
+    ///      lookup table address
+    ///   5. `[WRITE, OPTIONAL]` Registry account of the authority
+    CreateLookupTableWithExpiry {
+        /// A recent slot must be used in the derivation path
+        /// for each initialized table. When closing table accounts,
+        /// the initialization slot must no longer be "recent" to prevent
+        /// address tables from being recreated with reordered or
+        /// otherwise malicious addresses.
+        recent_slot: Slot,
+        /// Address tables are always initialized at program-derived
+        /// addresses using the funding address, recent blockhash, and
+        /// the user-passed `bump_seed`.
+        bump_seed: u8,
+        /// The slot at which the table will be deactivated. Must be after
+        /// the current slot.
+        deactivation_slot: Slot,
+    },
+
+    /// Permanently mark an address lookup table as append-only. The
+    /// authority can continue to extend the table, but the table can never
+    /// be deactivated or closed.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Address lookup table account to mark as append-only
+    ///   1. `[SIGNER]` Current authority
+    MakeLookupTableAppendOnly,
+
+    /// Set which signers are allowed to extend an address lookup table.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Address lookup table account to update
+    ///   1. `[SIGNER]` Current authority
+    SetLookupTableExtendMode {
+        /// The new extend mode of the table.
+        extend_mode: LookupTableExtendMode,
+    },
+
+    /// Set or clear the program invoked with new addresses before they're
+    /// appended to an address lookup table. The hook is stored in the table's
+    /// settings account, so funding account and system program account
+    /// references are only required if the settings account doesn't exist
+    /// yet.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Address lookup table account to update
+    ///   1. `[SIGNER]` Current authority
+    ///   2. `[WRITE]` Settings account, derived from the address lookup table
+    ///      address
+    ///   3. `[SIGNER, WRITE, OPTIONAL]` Account that will fund the settings
+    ///      account
+    ///   4. `[OPTIONAL]` System program for CPI.
+    SetLookupTableExtendHook {
+        /// The program to invoke on every extension, or `None` to remove
+        /// the table's hook.
+        extend_hook: Option<Pubkey>,
+    },
+
+    /// Create an address lookup table which invokes an extend hook program
+    /// on every extension. Fails if the table already exists. The hook is
+    /// stored in the table's settings account, which is created as well.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Uninitialized address lookup table account
+    ///   1. `[]` Account used to derive and control the new address lookup table.
+    ///   2. `[SIGNER, WRITE]` Account that will fund the new address lookup
+    ///      table and its settings account.
+    ///   3. `[]` System program for CPI.
+    ///   4. `[WRITE]` Uninitialized settings account, derived from the address
+    ///      lookup table address
+    ///   5. `[WRITE, OPTIONAL]` Registry account of the authority
+    CreateLookupTableWithExtendHook {
+        /// A recent slot must be used in the derivation path
+        /// for each initialized table. When closing table accounts,
+        /// the initialization slot must no longer be "recent" to prevent
+        /// address tables from being recreated with reordered or
+        /// otherwise malicious addresses.
+        recent_slot: Slot,
+        /// Address tables are always initialized at program-derived
+        /// addresses using the funding address, recent blockhash, and
+        /// the user-passed `bump_seed`.
+        bump_seed: u8,
+        /// The program to invoke on every extension.
+        extend_hook: Pubkey,
+    },
+
+    /// Start recording the lamports each payer contributes to extend an
+    /// address lookup table by creating the table's funders account, so
+    /// that funders are refunded when the table is closed. Only extensions
+    /// signed by the current authority are recorded. At most
+    /// `LOOKUP_TABLE_MAX_FUNDERS` funders are recorded, after which only
+    /// recorded funders can fund the authority's extensions.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Address lookup table account to record funders for
+    ///   1. `[SIGNER]` Current authority
+    ///   2. `[WRITE]` Uninitialized funders account, derived from the address
+    ///      lookup table address
+    ///   3. `[SIGNER, WRITE]` Account that will fund the funders account
+    ///   4. `[]` System program for CPI.
+    InitializeLookupTableFunders {
+        /// Funders accounts are always initialized at the canonical
+        /// program-derived address of the lookup table address, so
+        /// `bump_seed` must be the canonical bump seed.
+        bump_seed: u8,
+    },
+
+    /// Freeze an address lookup table until a slot. The table behaves
+    /// exactly as a frozen table until the slot is reached, after which the
+    /// current authority regains control. The slot is stored in the table's
+    /// settings account, so funding account and system program account
+    /// references are only required if the settings account doesn't exist
+    /// yet.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Address lookup table account to freeze
+    ///   1. `[SIGNER]` Current authority
+    ///   2. `[WRITE]` Settings account, derived from the address lookup table
+    ///      address
+    ///   3. `[SIGNER, WRITE, OPTIONAL]` Account that will fund the settings
+    ///      account
+    ///   4. `[OPTIONAL]` System program for CPI.
+    FreezeLookupTableUntil {
+        /// The slot at which the authority regains control. Must be after
+        /// the current slot.
+        slot: Slot,
+    },
+
+    /// Create the labels account of an address lookup table, which stores
+    /// human-readable labels for table indexes and a table description.
+    ///
+    /// # Account references
+    ///   0. `[]` Address lookup table account to label
+    ///   1. `[SIGNER]` Current authority
+    ///   2. `[WRITE]` Uninitialized labels account, derived from the address
+    ///      lookup table address
+    ///   3. `[SIGNER, WRITE]` Account that will fund the labels account
+    ///   4. `[]` System program for CPI.
+    InitializeLookupTableLabels {
+        /// Labels accounts are always initialized at program-derived
+        /// addresses using the lookup table address and the user-passed
+        /// `bump_seed`.
+        bump_seed: u8,
+    },
+
+    /// Set the label of the address stored at an index of an address lookup
+    /// table, replacing any existing label. Labels can only be changed under
+    /// the same rules as extending the table with its authority. Funding
+    /// account and system program account references are only required if
+    /// the labels account requires additional lamports to cover the
+    /// rent-exempt balance after being reallocated. If the labels account
+    /// shrinks instead, the lamports it no longer needs are returned to the
+    /// funding account.
+    ///
+    /// # Account references
+    ///   0. `[]` Address lookup table account to label
+    ///   1. `[SIGNER]` Current authority
+    ///   2. `[WRITE]` Labels account of the address lookup table
+    ///   3. `[SIGNER, WRITE, OPTIONAL]` Account that will fund the labels
+    ///      account reallocation, or receive the lamports it frees
+    ///   4. `[OPTIONAL]` System program for CPI.
+    SetLookupTableLabel {
+        /// The table index of the labeled address.
+        index: u8,
+        /// The label, of at most `LOOKUP_TABLE_MAX_LABEL_LEN` bytes.
+        label: String,
+    },
+
+    /// Clear the label of the address stored at an index of an address
+    /// lookup table. The lamports freed by shrinking the labels account are
+    /// returned to the recipient account, if one is provided.
+    ///
+    /// # Account references
+    ///   0. `[]` Address lookup table account to label
+    ///   1. `[SIGNER]` Current authority
+    ///   2. `[WRITE]` Labels account of the address lookup table
+    ///   3. `[WRITE, OPTIONAL]` Recipient of the freed lamports
+    ClearLookupTableLabel {
+        /// The table index of the labeled address.
+        index: u8,
+    },
+
+    /// Set the description of an address lookup table, under the same rules
+    /// as `SetLookupTableLabel`.
+    ///
+    /// # Account references
+    ///   0. `[]` Address lookup table account to describe
+    ///   1. `[SIGNER]` Current authority
+    ///   2. `[WRITE]` Labels account of the address lookup table
+    ///   3. `[SIGNER, WRITE, OPTIONAL]` Account that will fund the labels
+    ///      account reallocation, or receive the lamports it frees
+    ///   4. `[OPTIONAL]` System program for CPI.
+    SetLookupTableDescription {
+        /// The description, of at most `LOOKUP_TABLE_MAX_DESCRIPTION_LEN`
+        /// bytes.
+        description: String,
+    },
+
+    /// Record the table which replaces a retired address lookup table, so
+    /// that consumers can migrate to it. Only tables which are scheduled for
+    /// deactivation, deactivating, or frozen until a slot can record a
+    /// successor. Permanently frozen tables record their successor with
+    /// `FreezeLookupTableWithSuccessor` instead. The successor is stored in
+    /// the table's settings account, so funding account and system program
+    /// account references are only required if the settings account doesn't
+    /// exist yet.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Address lookup table account to update
+    ///   1. `[SIGNER]` Current authority
+    ///   2. `[WRITE]` Settings account, derived from the address lookup table
+    ///      address
+    ///   3. `[SIGNER, WRITE, OPTIONAL]` Account that will fund the settings
+    ///      account
+    ///   4. `[OPTIONAL]` System program for CPI.
+    SetLookupTableSuccessor {
+        /// The address of the table which replaces this table.
+        successor: Pubkey,
+    },
+
+    /// Create the registry account of an authority, which lists the lookup
+    /// tables created with the registry account so that they can be
+    /// enumerated. Tables are removed from the registry when they're closed
+    /// or frozen.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Uninitialized registry account, derived from the
+    ///      authority address
+    ///   1. `[SIGNER]` Authority whose lookup tables are registered
+    ///   2. `[SIGNER, WRITE]` Account that will fund the registry account
+    ///   3. `[]` System program for CPI.
+    InitializeLookupTableRegistry {
+        /// Registry accounts are always initialized at the canonical
+        /// program-derived address of the authority address, so
+        /// `bump_seed` must be the canonical bump seed.
+        bump_seed: u8,
+    },
+
+    /// Deactivate an address lookup table whose scheduled deactivation slot
+    /// has been reached. Any account can pay for the transaction, since the
+    /// authority already scheduled the deactivation. The table is
+    /// deactivated in the current slot, so it still goes through the full
+    /// deactivation cool-down.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Address lookup table account to deactivate
+    ///   1. `[WRITE]` Settings account, derived from the address lookup table
+    ///      address
+    DeactivateScheduledLookupTable,
+
+    /// Close the labels account of an address lookup table and reclaim its
+    /// lamports. Only the authority which initialized the labels can close
+    /// them, which remains possible after the table is frozen or closed.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Labels account to close
+    ///   1. `[SIGNER]` Authority which initialized the labels account
+    ///   2. `[WRITE]` Recipient of closed account lamports
+    CloseLookupTableLabels,
+
+    /// Permanently freeze an address lookup table as with `FreezeLookupTable`,
+    /// recording the table which replaces it first, since frozen tables no
+    /// longer have an authority that could record a successor. Funding
+    /// account and system program account references are only required if
+    /// the settings account doesn't exist yet.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Address lookup table account to freeze
+    ///   1. `[SIGNER]` Current authority
+    ///   2. `[WRITE]` Settings account, derived from the address lookup table
+    ///      address
+    ///   3. `[SIGNER, WRITE, OPTIONAL]` Account that will fund the settings
+    ///      account
+    ///   4. `[OPTIONAL]` System program for CPI.
+    ///
+    /// The registry account of the authority, if the table is registered, can
+    /// be passed after the other account references.
+    FreezeLookupTableWithSuccessor {
+        /// The address of the table which replaces this table.
+        successor: Pubkey,
+    },
+}
+
+/// Instructions which extend hook programs must support
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub enum ExtendHookInstruction {
+    /// Validate addresses about to be appended to an address lookup table.
+    /// Failing rejects the extension.
+    ///
+    /// All accounts of the extend instruction are forwarded to the hook
+    /// program as read-only, non-signer accounts, in the same order.
+    ///
+    /// # Account references
+    ///   0. `[]` Address lookup table account being extended
+    ///   1. `[]` Account which signed for the extension
+    ///   2. ..`2+N` `[]` Remaining accounts of the extend instruction
+    Execute {
+        /// The addresses about to be appended to the table.
+        new_addresses: Vec<Pubkey>,
+    },
 }
 
 /// Derives the address of an address table account from a wallet address and a
@@ -83,6 +691,38 @@ pub fn derive_lookup_table_address(
     )
 }
 
+/// Derives the address of the funders account of an address table account.
+pub fn derive_lookup_table_funders_address(lookup_table_address: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[LOOKUP_TABLE_FUNDERS_SEED, lookup_table_address.as_ref()],
+        &crate::id(),
+    )
+}
+
+/// Derives the address of the labels account of an address table account.
+pub fn derive_lookup_table_labels_address(lookup_table_address: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[LOOKUP_TABLE_LABELS_SEED, lookup_table_address.as_ref()],
+        &crate::id(),
+    )
+}
+
+/// Derives the address of the settings account of an address table account.
+pub fn derive_lookup_table_settings_address(lookup_table_address: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[LOOKUP_TABLE_SETTINGS_SEED, lookup_table_address.as_ref()],
+        &crate::id(),
+    )
+}
+
+/// Derives the address of the lookup table registry account of an authority.
+pub fn derive_lookup_table_registry_address(authority_address: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[LOOKUP_TABLE_REGISTRY_SEED, authority_address.as_ref()],
+        &crate::id(),
+    )
+}
+
 // [Core BPF]: `create_lookup_table_signed` has been removed, since feature
 // "FKAcEvNgSY79RpqsPNUV5gDyumopH4cEHqUxyfm8b8Ap"
 // (relax_authority_signer_check_for_lookup_table_creation) has been activated
@@ -115,31 +755,502 @@ pub fn create_lookup_table(
     (instruction, lookup_table_address)
 }
 
-/// Constructs an instruction that freezes an address lookup
-/// table so that it can never be closed or extended again. Empty
-/// lookup tables cannot be frozen.
-pub fn freeze_lookup_table(lookup_table_address: Pubkey, authority_address: Pubkey) -> Instruction {
-    Instruction::new_with_bincode(
-        crate::id(),
-        &ProgramInstruction::FreezeLookupTable,
-        vec![
-            AccountMeta::new(lookup_table_address, false),
-            AccountMeta::new_readonly(authority_address, true),
-        ],
-    )
+/// Constructs an instruction to create a table account which is added to the
+/// authority's registry and returns the instruction and the table account's
+/// derived address.
+pub fn create_lookup_table_with_registry(
+    authority_address: Pubkey,
+    payer_address: Pubkey,
+    recent_slot: Slot,
+) -> (Instruction, Pubkey) {
+    let (mut instruction, lookup_table_address) =
+        create_lookup_table(authority_address, payer_address, recent_slot);
+    let (registry_address, _) = derive_lookup_table_registry_address(&authority_address);
+    instruction
+        .accounts
+        .push(AccountMeta::new(registry_address, false));
+
+    (instruction, lookup_table_address)
 }
 
-/// Constructs an instruction which extends an address lookup
-/// table account with new addresses.
-pub fn extend_lookup_table(
-    lookup_table_address: Pubkey,
+/// Constructs an instruction to create a table account initialized with the
+/// provided addresses and returns the instruction and the table account's
+/// derived address.
+pub fn create_lookup_table_with_addresses(
     authority_address: Pubkey,
-    payer_address: Option<Pubkey>,
-    new_addresses: Vec<Pubkey>,
-) -> Instruction {
-    let mut accounts = vec![
-        AccountMeta::new(lookup_table_address, false),
+    payer_address: Pubkey,
+    recent_slot: Slot,
+    addresses: Vec<Pubkey>,
+) -> (Instruction, Pubkey) {
+    let (lookup_table_address, bump_seed) =
+        derive_lookup_table_address(&authority_address, recent_slot);
+
+    let instruction = Instruction::new_with_bincode(
+        crate::id(),
+        &ProgramInstruction::CreateLookupTableWithAddresses {
+            recent_slot,
+            bump_seed,
+            addresses,
+        },
+        vec![
+            AccountMeta::new(lookup_table_address, false),
+            AccountMeta::new_readonly(authority_address, false),
+            AccountMeta::new(payer_address, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    );
+
+    (instruction, lookup_table_address)
+}
+
+/// Constructs an instruction to create a table account with explicit behavior
+/// for an existing table account and returns the instruction and the table
+/// account's derived address.
+pub fn create_lookup_table_checked(
+    authority_address: Pubkey,
+    payer_address: Pubkey,
+    recent_slot: Slot,
+    mode: CreateLookupTableMode,
+) -> (Instruction, Pubkey) {
+    let (lookup_table_address, bump_seed) =
+        derive_lookup_table_address(&authority_address, recent_slot);
+
+    let instruction = Instruction::new_with_bincode(
+        crate::id(),
+        &ProgramInstruction::CreateLookupTableChecked {
+            recent_slot,
+            bump_seed,
+            mode,
+        },
+        vec![
+            AccountMeta::new(lookup_table_address, false),
+            AccountMeta::new_readonly(authority_address, false),
+            AccountMeta::new(payer_address, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    );
+
+    (instruction, lookup_table_address)
+}
+
+/// Constructs an instruction to create a table account which will be
+/// deactivated once `deactivation_slot` is reached and returns the
+/// instruction and the table account's derived address.
+pub fn create_lookup_table_with_expiry(
+    authority_address: Pubkey,
+    payer_address: Pubkey,
+    recent_slot: Slot,
+    deactivation_slot: Slot,
+) -> (Instruction, Pubkey) {
+    let (lookup_table_address, bump_seed) =
+        derive_lookup_table_address(&authority_address, recent_slot);
+
+    let instruction = Instruction::new_with_bincode(
+        crate::id(),
+        &ProgramInstruction::CreateLookupTableWithExpiry {
+            recent_slot,
+            bump_seed,
+            deactivation_slot,
+        },
+        vec![
+            AccountMeta::new(lookup_table_address, false),
+            AccountMeta::new_readonly(authority_address, false),
+            AccountMeta::new(payer_address, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(
+                derive_lookup_table_settings_address(&lookup_table_address).0,
+                false,
+            ),
+        ],
+    );
+
+    (instruction, lookup_table_address)
+}
+
+/// Constructs an instruction to create a table account which invokes
+/// `extend_hook` on every extension and returns the instruction and
+/// the table account's derived address.
+pub fn create_lookup_table_with_extend_hook(
+    authority_address: Pubkey,
+    payer_address: Pubkey,
+    recent_slot: Slot,
+    extend_hook: Pubkey,
+) -> (Instruction, Pubkey) {
+    let (lookup_table_address, bump_seed) =
+        derive_lookup_table_address(&authority_address, recent_slot);
+
+    let instruction = Instruction::new_with_bincode(
+        crate::id(),
+        &ProgramInstruction::CreateLookupTableWithExtendHook {
+            recent_slot,
+            bump_seed,
+            extend_hook,
+        },
+        vec![
+            AccountMeta::new(lookup_table_address, false),
+            AccountMeta::new_readonly(authority_address, false),
+            AccountMeta::new(payer_address, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(
+                derive_lookup_table_settings_address(&lookup_table_address).0,
+                false,
+            ),
+        ],
+    );
+
+    (instruction, lookup_table_address)
+}
+
+/// Constructs an instruction that freezes an address lookup
+/// table so that it can never be closed or extended again. Empty
+/// lookup tables cannot be frozen.
+pub fn freeze_lookup_table(lookup_table_address: Pubkey, authority_address: Pubkey) -> Instruction {
+    Instruction::new_with_bincode(
+        crate::id(),
+        &ProgramInstruction::FreezeLookupTable,
+        vec![
+            AccountMeta::new(lookup_table_address, false),
+            AccountMeta::new_readonly(authority_address, true),
+        ],
+    )
+}
+
+/// Constructs an instruction that freezes an address lookup
+/// table which is listed in its authority's registry, removing
+/// it from the registry.
+pub fn freeze_registered_lookup_table(
+    lookup_table_address: Pubkey,
+    authority_address: Pubkey,
+) -> Instruction {
+    let mut instruction = freeze_lookup_table(lookup_table_address, authority_address);
+    let (registry_address, _) = derive_lookup_table_registry_address(&authority_address);
+    instruction
+        .accounts
+        .push(AccountMeta::new(registry_address, false));
+    instruction
+}
+
+/// Constructs an instruction that freezes an address lookup
+/// table only if it contains exactly the expected addresses. Panics
+/// if more than `u16::MAX` addresses are expected.
+pub fn freeze_lookup_table_checked(
+    lookup_table_address: Pubkey,
+    authority_address: Pubkey,
+    expected_addresses: &[Pubkey],
+) -> Instruction {
+    Instruction::new_with_bincode(
+        crate::id(),
+        &ProgramInstruction::FreezeLookupTableChecked {
+            expected_addresses_len: u16::try_from(expected_addresses.len())
+                .expect("Expected addresses length must fit in a u16"),
+            expected_addresses_hash: AddressLookupTable::hash_addresses(expected_addresses),
+        },
+        vec![
+            AccountMeta::new(lookup_table_address, false),
+            AccountMeta::new_readonly(authority_address, true),
+        ],
+    )
+}
+
+/// Constructs an instruction that freezes an address lookup
+/// table until `slot`, after which the authority regains control.
+pub fn freeze_lookup_table_until(
+    lookup_table_address: Pubkey,
+    authority_address: Pubkey,
+    payer_address: Option<Pubkey>,
+    slot: Slot,
+) -> Instruction {
+    let (settings_address, _) = derive_lookup_table_settings_address(&lookup_table_address);
+    let mut accounts = vec![
+        AccountMeta::new(lookup_table_address, false),
+        AccountMeta::new_readonly(authority_address, true),
+        AccountMeta::new(settings_address, false),
+    ];
+
+    if let Some(payer_address) = payer_address {
+        accounts.extend([
+            AccountMeta::new(payer_address, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ]);
+    }
+
+    Instruction::new_with_bincode(
+        crate::id(),
+        &ProgramInstruction::FreezeLookupTableUntil { slot },
+        accounts,
+    )
+}
+
+/// Constructs an instruction which extends an address lookup
+/// table account with new addresses.
+pub fn extend_lookup_table(
+    lookup_table_address: Pubkey,
+    authority_address: Pubkey,
+    payer_address: Option<Pubkey>,
+    new_addresses: Vec<Pubkey>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(lookup_table_address, false),
+        AccountMeta::new_readonly(authority_address, true),
+    ];
+
+    if let Some(payer_address) = payer_address {
+        accounts.extend([
+            AccountMeta::new(payer_address, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ]);
+    }
+
+    Instruction::new_with_bincode(
+        crate::id(),
+        &ProgramInstruction::ExtendLookupTable { new_addresses },
+        accounts,
+    )
+}
+
+/// Constructs an instruction which reserves capacity for a total of
+/// `capacity` addresses in an address lookup table account.
+pub fn reserve_lookup_table_capacity(
+    lookup_table_address: Pubkey,
+    authority_address: Pubkey,
+    payer_address: Pubkey,
+    capacity: u16,
+) -> Instruction {
+    Instruction::new_with_bincode(
+        crate::id(),
+        &ProgramInstruction::ReserveLookupTableCapacity { capacity },
+        vec![
+            AccountMeta::new(lookup_table_address, false),
+            AccountMeta::new_readonly(authority_address, true),
+            AccountMeta::new(payer_address, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+/// Constructs an instruction which extends an address lookup
+/// table account with new addresses, failing unless the first new
+/// address will be stored at `expected_start_index`.
+pub fn extend_lookup_table_checked(
+    lookup_table_address: Pubkey,
+    authority_address: Pubkey,
+    payer_address: Option<Pubkey>,
+    new_addresses: Vec<Pubkey>,
+    expected_start_index: u8,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(lookup_table_address, false),
+        AccountMeta::new_readonly(authority_address, true),
+    ];
+
+    if let Some(payer_address) = payer_address {
+        accounts.extend([
+            AccountMeta::new(payer_address, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ]);
+    }
+
+    Instruction::new_with_bincode(
+        crate::id(),
+        &ProgramInstruction::ExtendLookupTableChecked {
+            new_addresses,
+            expected_start_index,
+        },
+        accounts,
+    )
+}
+
+/// Constructs an instruction which extends an address lookup
+/// table account with new addresses, succeeding without changes if
+/// the new addresses are already stored starting at
+/// `expected_start_index` at the end of the table.
+pub fn extend_lookup_table_idempotent(
+    lookup_table_address: Pubkey,
+    authority_address: Pubkey,
+    payer_address: Option<Pubkey>,
+    new_addresses: Vec<Pubkey>,
+    expected_start_index: u8,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(lookup_table_address, false),
+        AccountMeta::new_readonly(authority_address, true),
+    ];
+
+    if let Some(payer_address) = payer_address {
+        accounts.extend([
+            AccountMeta::new(payer_address, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ]);
+    }
+
+    Instruction::new_with_bincode(
+        crate::id(),
+        &ProgramInstruction::ExtendLookupTableIdempotent {
+            new_addresses,
+            expected_start_index,
+        },
+        accounts,
+    )
+}
+
+/// Constructs an instruction which extends an address lookup
+/// table account with new addresses passed as instruction accounts.
+/// Panics if more than `u16::MAX` new addresses are passed.
+pub fn extend_from_accounts(
+    lookup_table_address: Pubkey,
+    authority_address: Pubkey,
+    payer_address: Pubkey,
+    new_addresses: &[Pubkey],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(lookup_table_address, false),
+        AccountMeta::new_readonly(authority_address, true),
+        AccountMeta::new(payer_address, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    accounts.extend(
+        new_addresses
+            .iter()
+            .map(|address| AccountMeta::new_readonly(*address, false)),
+    );
+
+    Instruction::new_with_bincode(
+        crate::id(),
+        &ProgramInstruction::ExtendFromAccounts {
+            num_addresses: u16::try_from(new_addresses.len())
+                .expect("New addresses length must fit in a u16"),
+        },
+        accounts,
+    )
+}
+
+/// Constructs an instruction which extends an address lookup
+/// table account with `num_addresses` addresses copied from the
+/// source address lookup table, starting at `start_index`.
+pub fn copy_from_lookup_table(
+    lookup_table_address: Pubkey,
+    authority_address: Pubkey,
+    source_lookup_table_address: Pubkey,
+    payer_address: Option<Pubkey>,
+    start_index: u8,
+    num_addresses: u16,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(lookup_table_address, false),
+        AccountMeta::new_readonly(authority_address, true),
+        AccountMeta::new_readonly(source_lookup_table_address, false),
+    ];
+
+    if let Some(payer_address) = payer_address {
+        accounts.extend([
+            AccountMeta::new(payer_address, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ]);
+    }
+
+    Instruction::new_with_bincode(
+        crate::id(),
+        &ProgramInstruction::CopyFromLookupTable {
+            start_index,
+            num_addresses,
+        },
+        accounts,
+    )
+}
+
+/// Constructs an instruction that permanently marks an address
+/// lookup table as append-only, so that it can never be deactivated
+/// or closed.
+pub fn make_lookup_table_append_only(
+    lookup_table_address: Pubkey,
+    authority_address: Pubkey,
+) -> Instruction {
+    Instruction::new_with_bincode(
+        crate::id(),
+        &ProgramInstruction::MakeLookupTableAppendOnly,
+        vec![
+            AccountMeta::new(lookup_table_address, false),
+            AccountMeta::new_readonly(authority_address, true),
+        ],
+    )
+}
+
+/// Constructs an instruction that creates the funders account of an
+/// address lookup table, so that the lamports each payer contributes
+/// to extend the table are recorded and refunded on close.
+pub fn initialize_lookup_table_funders(
+    lookup_table_address: Pubkey,
+    authority_address: Pubkey,
+    payer_address: Pubkey,
+) -> Instruction {
+    let (funders_address, bump_seed) = derive_lookup_table_funders_address(&lookup_table_address);
+
+    Instruction::new_with_bincode(
+        crate::id(),
+        &ProgramInstruction::InitializeLookupTableFunders { bump_seed },
+        vec![
+            AccountMeta::new(lookup_table_address, false),
+            AccountMeta::new_readonly(authority_address, true),
+            AccountMeta::new(funders_address, false),
+            AccountMeta::new(payer_address, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+/// Constructs an instruction which extends an address lookup
+/// table account that records its funders with new addresses,
+/// recording the lamports contributed by the payer.
+pub fn extend_lookup_table_with_funders(
+    lookup_table_address: Pubkey,
+    authority_address: Pubkey,
+    payer_address: Pubkey,
+    new_addresses: Vec<Pubkey>,
+) -> Instruction {
+    let mut instruction = extend_lookup_table(
+        lookup_table_address,
+        authority_address,
+        Some(payer_address),
+        new_addresses,
+    );
+    let (funders_address, _) = derive_lookup_table_funders_address(&lookup_table_address);
+    instruction
+        .accounts
+        .push(AccountMeta::new(funders_address, false));
+    instruction
+}
+
+/// Constructs an instruction that sets which signers are allowed
+/// to extend an address lookup table.
+pub fn set_lookup_table_extend_mode(
+    lookup_table_address: Pubkey,
+    authority_address: Pubkey,
+    extend_mode: LookupTableExtendMode,
+) -> Instruction {
+    Instruction::new_with_bincode(
+        crate::id(),
+        &ProgramInstruction::SetLookupTableExtendMode { extend_mode },
+        vec![
+            AccountMeta::new(lookup_table_address, false),
+            AccountMeta::new_readonly(authority_address, true),
+        ],
+    )
+}
+
+/// Constructs an instruction that sets or clears the program
+/// invoked before new addresses are appended to an address lookup
+/// table.
+pub fn set_lookup_table_extend_hook(
+    lookup_table_address: Pubkey,
+    authority_address: Pubkey,
+    payer_address: Option<Pubkey>,
+    extend_hook: Option<Pubkey>,
+) -> Instruction {
+    let (settings_address, _) = derive_lookup_table_settings_address(&lookup_table_address);
+    let mut accounts = vec![
+        AccountMeta::new(lookup_table_address, false),
         AccountMeta::new_readonly(authority_address, true),
+        AccountMeta::new(settings_address, false),
     ];
 
     if let Some(payer_address) = payer_address {
@@ -151,11 +1262,63 @@ pub fn extend_lookup_table(
 
     Instruction::new_with_bincode(
         crate::id(),
-        &ProgramInstruction::ExtendLookupTable { new_addresses },
+        &ProgramInstruction::SetLookupTableExtendHook { extend_hook },
         accounts,
     )
 }
 
+/// Constructs an instruction that records the table which replaces
+/// a retired address lookup table.
+pub fn set_lookup_table_successor(
+    lookup_table_address: Pubkey,
+    authority_address: Pubkey,
+    payer_address: Option<Pubkey>,
+    successor: Pubkey,
+) -> Instruction {
+    Instruction::new_with_bincode(
+        crate::id(),
+        &ProgramInstruction::SetLookupTableSuccessor { successor },
+        lookup_table_settings_accounts(lookup_table_address, authority_address, payer_address),
+    )
+}
+
+/// Constructs an instruction that records the table which replaces
+/// an address lookup table and then permanently freezes it.
+pub fn freeze_lookup_table_with_successor(
+    lookup_table_address: Pubkey,
+    authority_address: Pubkey,
+    payer_address: Option<Pubkey>,
+    successor: Pubkey,
+) -> Instruction {
+    Instruction::new_with_bincode(
+        crate::id(),
+        &ProgramInstruction::FreezeLookupTableWithSuccessor { successor },
+        lookup_table_settings_accounts(lookup_table_address, authority_address, payer_address),
+    )
+}
+
+fn lookup_table_settings_accounts(
+    lookup_table_address: Pubkey,
+    authority_address: Pubkey,
+    payer_address: Option<Pubkey>,
+) -> Vec<AccountMeta> {
+    let (settings_address, _) = derive_lookup_table_settings_address(&lookup_table_address);
+    let mut accounts = vec![
+        AccountMeta::new(lookup_table_address, false),
+        AccountMeta::new_readonly(authority_address, true),
+        AccountMeta::new(settings_address, false),
+    ];
+
+    if let Some(payer_address) = payer_address {
+        accounts.extend([
+            AccountMeta::new(payer_address, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ]);
+    }
+
+    accounts
+}
+
 /// Constructs an instruction that deactivates an address lookup
 /// table so that it cannot be extended again and will be unusable
 /// and eligible for closure after a short amount of time.
@@ -191,3 +1354,291 @@ pub fn close_lookup_table(
         ],
     )
 }
+
+/// Constructs an instruction that creates the labels account of an
+/// address lookup table.
+pub fn initialize_lookup_table_labels(
+    lookup_table_address: Pubkey,
+    authority_address: Pubkey,
+    payer_address: Pubkey,
+) -> Instruction {
+    let (labels_address, bump_seed) = derive_lookup_table_labels_address(&lookup_table_address);
+
+    Instruction::new_with_bincode(
+        crate::id(),
+        &ProgramInstruction::InitializeLookupTableLabels { bump_seed },
+        vec![
+            AccountMeta::new_readonly(lookup_table_address, false),
+            AccountMeta::new_readonly(authority_address, true),
+            AccountMeta::new(labels_address, false),
+            AccountMeta::new(payer_address, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+fn lookup_table_labels_accounts(
+    lookup_table_address: Pubkey,
+    authority_address: Pubkey,
+    payer_address: Option<Pubkey>,
+) -> Vec<AccountMeta> {
+    let (labels_address, _) = derive_lookup_table_labels_address(&lookup_table_address);
+    let mut accounts = vec![
+        AccountMeta::new_readonly(lookup_table_address, false),
+        AccountMeta::new_readonly(authority_address, true),
+        AccountMeta::new(labels_address, false),
+    ];
+
+    if let Some(payer_address) = payer_address {
+        accounts.extend([
+            AccountMeta::new(payer_address, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ]);
+    }
+
+    accounts
+}
+
+/// Constructs an instruction that sets the label of the address
+/// stored at `index` of an address lookup table.
+pub fn set_lookup_table_label(
+    lookup_table_address: Pubkey,
+    authority_address: Pubkey,
+    payer_address: Option<Pubkey>,
+    index: u8,
+    label: String,
+) -> Instruction {
+    Instruction::new_with_bincode(
+        crate::id(),
+        &ProgramInstruction::SetLookupTableLabel { index, label },
+        lookup_table_labels_accounts(lookup_table_address, authority_address, payer_address),
+    )
+}
+
+/// Constructs an instruction that clears the label of the address
+/// stored at `index` of an address lookup table and returns the
+/// freed lamports to the recipient.
+pub fn clear_lookup_table_label(
+    lookup_table_address: Pubkey,
+    authority_address: Pubkey,
+    recipient_address: Pubkey,
+    index: u8,
+) -> Instruction {
+    let mut accounts = lookup_table_labels_accounts(lookup_table_address, authority_address, None);
+    accounts.push(AccountMeta::new(recipient_address, false));
+    Instruction::new_with_bincode(
+        crate::id(),
+        &ProgramInstruction::ClearLookupTableLabel { index },
+        accounts,
+    )
+}
+
+/// Constructs an instruction that closes the labels account of an
+/// address lookup table.
+pub fn close_lookup_table_labels(
+    lookup_table_address: Pubkey,
+    authority_address: Pubkey,
+    recipient_address: Pubkey,
+) -> Instruction {
+    let (labels_address, _) = derive_lookup_table_labels_address(&lookup_table_address);
+    Instruction::new_with_bincode(
+        crate::id(),
+        &ProgramInstruction::CloseLookupTableLabels,
+        vec![
+            AccountMeta::new(labels_address, false),
+            AccountMeta::new_readonly(authority_address, true),
+            AccountMeta::new(recipient_address, false),
+        ],
+    )
+}
+
+/// Constructs an instruction that sets the description of an
+/// address lookup table.
+pub fn set_lookup_table_description(
+    lookup_table_address: Pubkey,
+    authority_address: Pubkey,
+    payer_address: Option<Pubkey>,
+    description: String,
+) -> Instruction {
+    Instruction::new_with_bincode(
+        crate::id(),
+        &ProgramInstruction::SetLookupTableDescription { description },
+        lookup_table_labels_accounts(lookup_table_address, authority_address, payer_address),
+    )
+}
+
+/// Returns an instruction that closes an address lookup table
+/// account which records its funders. Each funder is refunded from
+/// the table's lamports and the rest will be drained to the
+/// recipient address.
+pub fn close_lookup_table_with_funders(
+    lookup_table_address: Pubkey,
+    authority_address: Pubkey,
+    recipient_address: Pubkey,
+    funder_addresses: &[Pubkey],
+) -> Instruction {
+    let mut instruction =
+        close_lookup_table(lookup_table_address, authority_address, recipient_address);
+    let (funders_address, _) = derive_lookup_table_funders_address(&lookup_table_address);
+    instruction
+        .accounts
+        .push(AccountMeta::new(funders_address, false));
+    instruction.accounts.extend(
+        funder_addresses
+            .iter()
+            .map(|funder_address| AccountMeta::new(*funder_address, false)),
+    );
+    instruction
+}
+
+/// Constructs an instruction that closes an address lookup
+/// table account which is listed in its authority's registry,
+/// removing it from the registry.
+pub fn close_registered_lookup_table(
+    lookup_table_address: Pubkey,
+    authority_address: Pubkey,
+    recipient_address: Pubkey,
+) -> Instruction {
+    let mut instruction =
+        close_lookup_table(lookup_table_address, authority_address, recipient_address);
+    let (registry_address, _) = derive_lookup_table_registry_address(&authority_address);
+    instruction
+        .accounts
+        .push(AccountMeta::new(registry_address, false));
+    instruction
+}
+
+/// Constructs an instruction that creates the lookup table
+/// registry account of an authority.
+pub fn initialize_lookup_table_registry(
+    authority_address: Pubkey,
+    payer_address: Pubkey,
+) -> Instruction {
+    let (registry_address, bump_seed) = derive_lookup_table_registry_address(&authority_address);
+
+    Instruction::new_with_bincode(
+        crate::id(),
+        &ProgramInstruction::InitializeLookupTableRegistry { bump_seed },
+        vec![
+            AccountMeta::new(registry_address, false),
+            AccountMeta::new_readonly(authority_address, true),
+            AccountMeta::new(payer_address, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+/// Constructs an instruction that schedules an address lookup
+/// table to be deactivated once `deactivation_slot` is reached.
+pub fn deactivate_lookup_table_at_slot(
+    lookup_table_address: Pubkey,
+    authority_address: Pubkey,
+    payer_address: Option<Pubkey>,
+    deactivation_slot: Slot,
+) -> Instruction {
+    let (settings_address, _) = derive_lookup_table_settings_address(&lookup_table_address);
+    let mut accounts = vec![
+        AccountMeta::new(lookup_table_address, false),
+        AccountMeta::new_readonly(authority_address, true),
+        AccountMeta::new(settings_address, false),
+    ];
+
+    if let Some(payer_address) = payer_address {
+        accounts.extend([
+            AccountMeta::new(payer_address, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ]);
+    }
+
+    Instruction::new_with_bincode(
+        crate::id(),
+        &ProgramInstruction::DeactivateLookupTableAtSlot { deactivation_slot },
+        accounts,
+    )
+}
+
+/// Constructs an instruction that deactivates an address lookup
+/// table whose scheduled deactivation slot has been reached.
+pub fn deactivate_scheduled_lookup_table(lookup_table_address: Pubkey) -> Instruction {
+    let (settings_address, _) = derive_lookup_table_settings_address(&lookup_table_address);
+    Instruction::new_with_bincode(
+        crate::id(),
+        &ProgramInstruction::DeactivateScheduledLookupTable,
+        vec![
+            AccountMeta::new(lookup_table_address, false),
+            AccountMeta::new(settings_address, false),
+        ],
+    )
+}
+
+/// Constructs an instruction that deactivates multiple address
+/// lookup table accounts controlled by the same authority. Panics
+/// if more than `u8::MAX` lookup tables are passed.
+pub fn deactivate_lookup_tables(
+    authority_address: Pubkey,
+    lookup_table_addresses: &[Pubkey],
+) -> Instruction {
+    let mut accounts = vec![AccountMeta::new_readonly(authority_address, true)];
+    accounts.extend(
+        lookup_table_addresses
+            .iter()
+            .map(|lookup_table_address| AccountMeta::new(*lookup_table_address, false)),
+    );
+
+    Instruction::new_with_bincode(
+        crate::id(),
+        &ProgramInstruction::DeactivateLookupTables {
+            num_lookup_tables: u8::try_from(lookup_table_addresses.len())
+                .expect("Lookup tables length must fit in a u8"),
+        },
+        accounts,
+    )
+}
+
+/// Returns an instruction that closes multiple address lookup
+/// table accounts controlled by the same authority. The accounts
+/// will be deallocated and the lamports will be drained to the
+/// recipient address. Panics if more than `u8::MAX` lookup tables
+/// are passed.
+pub fn close_lookup_tables(
+    authority_address: Pubkey,
+    recipient_address: Pubkey,
+    lookup_table_addresses: &[Pubkey],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(authority_address, true),
+        AccountMeta::new(recipient_address, false),
+    ];
+    accounts.extend(
+        lookup_table_addresses
+            .iter()
+            .map(|lookup_table_address| AccountMeta::new(*lookup_table_address, false)),
+    );
+
+    Instruction::new_with_bincode(
+        crate::id(),
+        &ProgramInstruction::CloseLookupTables {
+            num_lookup_tables: u8::try_from(lookup_table_addresses.len())
+                .expect("Lookup tables length must fit in a u8"),
+        },
+        accounts,
+    )
+}
+
+/// Returns an instruction that withdraws all lamports above the rent-exempt
+/// balance of an address lookup table account to the recipient address.
+pub fn withdraw_excess_lamports(
+    lookup_table_address: Pubkey,
+    authority_address: Pubkey,
+    recipient_address: Pubkey,
+) -> Instruction {
+    Instruction::new_with_bincode(
+        crate::id(),
+        &ProgramInstruction::WithdrawExcessLamports,
+        vec![
+            AccountMeta::new(lookup_table_address, false),
+            AccountMeta::new_readonly(authority_address, true),
+            AccountMeta::new(recipient_address, false),
+        ],
+    )
+}
@@ -3,11 +3,19 @@
 #![allow(incomplete_features)]
 #![cfg_attr(RUSTC_WITH_SPECIALIZATION, feature(specialization))]
 
+#[cfg(not(target_os = "solana"))]
+pub mod cache;
+#[cfg(not(target_os = "solana"))]
+pub mod client;
 #[cfg(not(feature = "no-entrypoint"))]
 mod entrypoint;
 pub mod error;
+#[cfg(not(target_os = "solana"))]
+pub mod filters;
 pub mod instruction;
 pub mod processor;
+#[cfg(not(target_os = "solana"))]
+pub mod resolver;
 pub mod state;
 
 // [Core BPF]: TODO: Program-test will not overwrite existing built-ins.
@@ -3,18 +3,28 @@
 use {
     crate::{
         check_id,
-        instruction::ProgramInstruction,
+        error::{lookup_tables_error, AddressLookupError},
+        instruction::{
+            CreateLookupTableMode, CreateLookupTableOutcome, ExtendHookInstruction,
+            ProgramInstruction,
+        },
         state::{
-            AddressLookupTable, LookupTableStatus, ProgramState, LOOKUP_TABLE_MAX_ADDRESSES,
-            LOOKUP_TABLE_META_SIZE,
+            lookup_table_data_len, rent_exempt_balance, AddressLookupTable, LookupTableExtendMode,
+            LookupTableFunders, LookupTableLabels, LookupTableMeta, LookupTableRegistry,
+            LookupTableSettings, LookupTableStatus, ProgramState, LOOKUP_TABLE_FUNDERS_SEED,
+            LOOKUP_TABLE_LABELS_SEED, LOOKUP_TABLE_MAX_ADDRESSES, LOOKUP_TABLE_MAX_DESCRIPTION_LEN,
+            LOOKUP_TABLE_MAX_LABEL_LEN, LOOKUP_TABLE_META_SIZE, LOOKUP_TABLE_REGISTRY_SEED,
+            LOOKUP_TABLE_SETTINGS_SEED, LOOKUP_TABLE_SETTINGS_SIZE,
         },
     },
     solana_program::{
         account_info::{next_account_info, AccountInfo},
         clock::{Clock, Slot},
         entrypoint::ProgramResult,
+        hash::Hash,
+        instruction::{AccountMeta, Instruction},
         msg,
-        program::{invoke, invoke_signed},
+        program::{invoke, invoke_signed, set_return_data},
         program_error::ProgramError,
         pubkey::{Pubkey, PUBKEY_BYTES},
         rent::Rent,
@@ -43,11 +53,36 @@ where
 // - Testnet:       Epoch 586
 // - Devnet:        Epoch 591
 // - Mainnet-Beta:  epoch 577
+/// Optional metadata for newly created lookup tables
+#[derive(Default)]
+struct NewLookupTableOptions {
+    deactivation_slot: Option<Slot>,
+    extend_hook: Option<Pubkey>,
+}
+
+impl NewLookupTableOptions {
+    /// Returns the settings of the new table, if any of the options are
+    /// stored in a settings account
+    fn settings(&self, lookup_table_key: &Pubkey) -> Option<LookupTableSettings> {
+        if self.deactivation_slot.is_none() && self.extend_hook.is_none() {
+            return None;
+        }
+        Some(LookupTableSettings {
+            extend_hook: self.extend_hook,
+            scheduled_deactivation_slot: self.deactivation_slot,
+            ..LookupTableSettings::new(*lookup_table_key)
+        })
+    }
+}
+
 fn process_create_lookup_table(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
     untrusted_recent_slot: Slot,
     bump_seed: u8,
+    initial_addresses: Vec<Pubkey>,
+    mode: Option<CreateLookupTableMode>,
+    options: NewLookupTableOptions,
 ) -> ProgramResult {
     let accounts_iter = &mut accounts.iter();
 
@@ -55,6 +90,12 @@ fn process_create_lookup_table(
     let authority_info = next_account_info(accounts_iter)?;
     let payer_info = next_account_info(accounts_iter)?;
     let _system_program_info = next_account_info(accounts_iter)?;
+    let settings = options.settings(lookup_table_info.key);
+    let settings_info = settings
+        .as_ref()
+        .map(|_| next_account_info(accounts_iter))
+        .transpose()?;
+    let registry_info = accounts_iter.next();
 
     if !payer_info.is_signer {
         msg!("Payer account must be a signer");
@@ -69,8 +110,8 @@ fn process_create_lookup_table(
     //
     // Note this will no longer consider skipped slots wherein a block was not
     // produced.
+    let clock = <Clock as Sysvar>::get()?;
     let derivation_slot = {
-        let clock = <Clock as Sysvar>::get()?;
         let oldest_possible_slot = clock.slot.saturating_sub(MAX_ENTRIES as u64);
         if untrusted_recent_slot > oldest_possible_slot && untrusted_recent_slot <= clock.slot {
             Ok(untrusted_recent_slot)
@@ -104,14 +145,66 @@ fn process_create_lookup_table(
     // mainnet-beta.
     // See https://github.com/solana-labs/solana/blob/e4064023bf7936ced97b0d4de22137742324983d/programs/address-lookup-table/src/processor.rs#L129-L135.
     if check_id(lookup_table_info.owner) {
-        return Ok(());
+        return match mode {
+            None => Ok(()),
+            Some(CreateLookupTableMode::Strict) => {
+                msg!("Lookup table already exists");
+                Err(ProgramError::AccountAlreadyInitialized)
+            }
+            Some(CreateLookupTableMode::Idempotent) => {
+                let lookup_table_data = lookup_table_info.try_borrow_data()?;
+                let lookup_table = AddressLookupTable::deserialize(&lookup_table_data)?;
+                if lookup_table.meta.authority != Some(*authority_info.key) {
+                    msg!("Lookup table already exists with a different authority");
+                    // [Core BPF]: TODO: Should be `ProgramError::IncorrectAuthority`
+                    // See https://github.com/solana-labs/solana/pull/35113
+                    return Err(ProgramError::Custom(0));
+                }
+                set_create_lookup_table_outcome(CreateLookupTableOutcome::AlreadyExists)
+            }
+        };
+    }
+
+    if let Some(deactivation_slot) = options.deactivation_slot {
+        if deactivation_slot <= clock.slot {
+            msg!(
+                "Deactivation slot {} must be after the current slot {}",
+                deactivation_slot,
+                clock.slot,
+            );
+            return Err(ProgramError::InvalidInstructionData);
+        }
+    }
+
+    if initial_addresses.len() > LOOKUP_TABLE_MAX_ADDRESSES {
+        msg!(
+            "Lookup table length {} would exceed max capacity of {}",
+            initial_addresses.len(),
+            LOOKUP_TABLE_MAX_ADDRESSES,
+        );
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let mut registry = registry_info
+        .map(|registry_info| {
+            load_lookup_table_registry(program_id, registry_info, authority_info.key)
+        })
+        .transpose()?;
+
+    let mut lookup_table_meta = LookupTableMeta::new(*authority_info.key);
+    lookup_table_meta.set_registered(registry.is_some());
+    if settings.is_some() {
+        lookup_table_meta.set_has_settings();
+    }
+    // Initial addresses are treated as if the table was extended in the
+    // current slot, so they can't be used for lookups until the next slot.
+    if !initial_addresses.is_empty() {
+        lookup_table_meta.last_extended_slot = clock.slot;
     }
 
-    let lookup_table_data_len = LOOKUP_TABLE_META_SIZE;
+    let lookup_table_data_len = lookup_table_data_len(initial_addresses.len())?;
     let rent = <Rent as Sysvar>::get()?;
-    let required_lamports = rent
-        .minimum_balance(lookup_table_data_len)
-        .max(1)
+    let required_lamports = rent_exempt_balance(&rent, lookup_table_data_len)
         .saturating_sub(lookup_table_info.lamports());
 
     if required_lamports > 0 {
@@ -141,15 +234,56 @@ fn process_create_lookup_table(
         ]],
     )?;
 
-    ProgramState::serialize_new_lookup_table(
-        *lookup_table_info.try_borrow_mut_data()?,
-        authority_info.key,
-    )?;
+    if lookup_table_meta == LookupTableMeta::new(*authority_info.key) {
+        ProgramState::serialize_new_lookup_table(
+            *lookup_table_info.try_borrow_mut_data()?,
+            authority_info.key,
+        )?;
+    } else {
+        let mut lookup_table_data = lookup_table_info.try_borrow_mut_data()?;
+        AddressLookupTable::overwrite_meta_data(&mut lookup_table_data, lookup_table_meta)?;
+        if !initial_addresses.is_empty() {
+            let uninitialized_addresses = AddressLookupTable::deserialize_addresses_from_index_mut(
+                &mut lookup_table_data,
+                LOOKUP_TABLE_META_SIZE,
+            )?;
+            uninitialized_addresses.copy_from_slice(&initial_addresses);
+        }
+    }
+
+    if let (Some(settings_info), Some(settings)) = (settings_info, settings) {
+        create_lookup_table_settings_account(
+            program_id,
+            lookup_table_info.key,
+            settings_info,
+            payer_info,
+        )?;
+        settings.overwrite_data(*settings_info.try_borrow_mut_data()?)?;
+    }
+
+    if let (Some(registry_info), Some(registry)) = (registry_info, registry.as_mut()) {
+        registry.register(*lookup_table_info.key);
+        overwrite_lookup_table_registry(registry_info, registry, Some(payer_info))?;
+    }
+
+    if mode == Some(CreateLookupTableMode::Idempotent) {
+        set_create_lookup_table_outcome(CreateLookupTableOutcome::Created)?;
+    }
+
+    Ok(())
+}
 
+fn set_create_lookup_table_outcome(outcome: CreateLookupTableOutcome) -> ProgramResult {
+    let return_data = bincode::serialize(&outcome).map_err(|_| ProgramError::InvalidAccountData)?;
+    set_return_data(&return_data);
     Ok(())
 }
 
-fn process_freeze_lookup_table(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+fn process_freeze_lookup_table(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    expected_addresses: Option<(u16, Hash)>,
+) -> ProgramResult {
     let accounts_iter = &mut accounts.iter();
 
     let lookup_table_info = next_account_info(accounts_iter)?;
@@ -168,7 +302,15 @@ fn process_freeze_lookup_table(program_id: &Pubkey, accounts: &[AccountInfo]) ->
         let lookup_table_data = lookup_table_info.try_borrow_data()?;
         let lookup_table = AddressLookupTable::deserialize(&lookup_table_data)?;
 
-        if lookup_table.meta.authority.is_none() {
+        let settings = load_lookup_table_settings(
+            program_id,
+            lookup_table_info.key,
+            &lookup_table.meta,
+            accounts,
+        )?;
+
+        let clock = <Clock as Sysvar>::get()?;
+        if lookup_table.meta.is_frozen(&settings, clock.slot) {
             msg!("Lookup table is already frozen");
             // [Core BPF]: TODO: Should be `ProgramError::Immutable`
             // See https://github.com/solana-labs/solana/pull/35113
@@ -179,7 +321,11 @@ fn process_freeze_lookup_table(program_id: &Pubkey, accounts: &[AccountInfo]) ->
             // See https://github.com/solana-labs/solana/pull/35113
             return Err(ProgramError::Custom(0));
         }
-        if lookup_table.meta.deactivation_slot != Slot::MAX {
+        // Tables scheduled for deactivation can't be frozen either, since a
+        // frozen table could never be closed once it deactivates.
+        if lookup_table.meta.deactivation_slot != Slot::MAX
+            || settings.scheduled_deactivation_slot.is_some()
+        {
             msg!("Deactivated tables cannot be frozen");
             return Err(ProgramError::InvalidArgument);
         }
@@ -187,11 +333,30 @@ fn process_freeze_lookup_table(program_id: &Pubkey, accounts: &[AccountInfo]) ->
             msg!("Empty lookup tables cannot be frozen");
             return Err(ProgramError::InvalidInstructionData);
         }
+        if let Some((expected_addresses_len, expected_addresses_hash)) = expected_addresses {
+            if lookup_table.addresses.len() != expected_addresses_len as usize
+                || AddressLookupTable::hash_addresses(&lookup_table.addresses)
+                    != expected_addresses_hash
+            {
+                msg!("Lookup table addresses do not match the expected addresses");
+                return Err(AddressLookupError::AddressesMismatch.into());
+            }
+        }
 
         lookup_table.meta
     };
 
+    if lookup_table_meta.is_registered() {
+        unregister_lookup_table(
+            program_id,
+            lookup_table_info.key,
+            authority_info.key,
+            accounts,
+        )?;
+    }
+
     lookup_table_meta.authority = None;
+    lookup_table_meta.set_registered(false);
     AddressLookupTable::overwrite_meta_data(
         *lookup_table_info.try_borrow_mut_data()?,
         lookup_table_meta,
@@ -200,10 +365,48 @@ fn process_freeze_lookup_table(program_id: &Pubkey, accounts: &[AccountInfo]) ->
     Ok(())
 }
 
+fn process_freeze_lookup_table_until(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    slot: Slot,
+) -> ProgramResult {
+    update_lookup_table(
+        program_id,
+        accounts,
+        |current_slot, lookup_table, settings| {
+            if slot <= current_slot {
+                msg!(
+                    "Freeze slot {} must be after the current slot {}",
+                    slot,
+                    current_slot,
+                );
+                return Err(ProgramError::InvalidInstructionData);
+            }
+            // The same restrictions as permanently freezing apply, since the
+            // table can't be closed while frozen.
+            if lookup_table.meta.deactivation_slot != Slot::MAX
+                || settings.scheduled_deactivation_slot.is_some()
+            {
+                msg!("Deactivated tables cannot be frozen");
+                return Err(ProgramError::InvalidArgument);
+            }
+            if lookup_table.addresses.is_empty() {
+                msg!("Empty lookup tables cannot be frozen");
+                return Err(ProgramError::InvalidInstructionData);
+            }
+
+            settings.frozen_until = Some(slot);
+            Ok(())
+        },
+    )
+}
+
 fn process_extend_lookup_table(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
-    new_addresses: Vec<Pubkey>,
+    mut new_addresses: Vec<Pubkey>,
+    expected_start_index: Option<u8>,
+    idempotent: bool,
 ) -> ProgramResult {
     let accounts_iter = &mut accounts.iter();
 
@@ -219,25 +422,70 @@ fn process_extend_lookup_table(
         return Err(ProgramError::MissingRequiredSignature);
     }
 
-    let (lookup_table_meta, old_table_data_len, new_table_data_len) = {
+    let (lookup_table_meta, extend_hook, new_addresses_start, new_table_data_len) = {
         let lookup_table_data = lookup_table_info.try_borrow_data()?;
         let mut lookup_table = AddressLookupTable::deserialize(&lookup_table_data)?;
+        let settings = load_lookup_table_settings(
+            program_id,
+            lookup_table_info.key,
+            &lookup_table.meta,
+            accounts,
+        )?;
 
-        if lookup_table.meta.authority.is_none() {
+        let clock = <Clock as Sysvar>::get()?;
+        if lookup_table.meta.is_frozen(&settings, clock.slot) {
             msg!("Lookup table is frozen");
             // [Core BPF]: TODO: Should be `ProgramError::Immutable`
             // See https://github.com/solana-labs/solana/pull/35113
             return Err(ProgramError::Custom(0));
         }
-        if lookup_table.meta.authority != Some(*authority_info.key) {
+        let extend_mode = lookup_table.meta.extend_mode();
+        if extend_mode == LookupTableExtendMode::Authority
+            && lookup_table.meta.authority != Some(*authority_info.key)
+        {
             // [Core BPF]: TODO: Should be `ProgramError::IncorrectAuthority`
             // See https://github.com/solana-labs/solana/pull/35113
             return Err(ProgramError::Custom(0));
         }
-        if lookup_table.meta.deactivation_slot != Slot::MAX {
+        if lookup_table.meta.status(clock.slot) != LookupTableStatus::Activated {
             msg!("Deactivated tables cannot be extended");
             return Err(ProgramError::InvalidArgument);
         }
+        if let Some(expected_start_index) = expected_start_index {
+            // A retried extension is a no-op if its addresses were already
+            // appended, even if the table has since become full.
+            if idempotent
+                && !new_addresses.is_empty()
+                && lookup_table.addresses.get(expected_start_index as usize..)
+                    == Some(new_addresses.as_slice())
+            {
+                msg!("Lookup table already contains the new addresses");
+                return Ok(());
+            }
+            if lookup_table.addresses.len() != expected_start_index as usize {
+                msg!(
+                    "Lookup table length {} does not match expected start index {}",
+                    lookup_table.addresses.len(),
+                    expected_start_index,
+                );
+                return Err(AddressLookupError::StartIndexMismatch.into());
+            }
+        }
+        if extend_mode == LookupTableExtendMode::OpenDeduplicated && !new_addresses.is_empty() {
+            let mut unique_addresses = Vec::with_capacity(new_addresses.len());
+            for new_address in new_addresses {
+                if !lookup_table.addresses.contains(&new_address)
+                    && !unique_addresses.contains(&new_address)
+                {
+                    unique_addresses.push(new_address);
+                }
+            }
+            if unique_addresses.is_empty() {
+                msg!("Lookup table already contains the new addresses");
+                return Ok(());
+            }
+            new_addresses = unique_addresses;
+        }
         if lookup_table.addresses.len() >= LOOKUP_TABLE_MAX_ADDRESSES {
             msg!("Lookup table is full and cannot contain more addresses");
             return Err(ProgramError::InvalidArgument);
@@ -260,7 +508,6 @@ fn process_extend_lookup_table(
             return Err(ProgramError::InvalidInstructionData);
         }
 
-        let clock = <Clock as Sysvar>::get()?;
         if clock.slot != lookup_table.meta.last_extended_slot {
             lookup_table.meta.last_extended_slot = clock.slot;
             lookup_table.meta.last_extended_slot_start_index =
@@ -271,37 +518,52 @@ fn process_extend_lookup_table(
                 })?;
         }
 
-        let old_table_data_len = LOOKUP_TABLE_META_SIZE
+        let new_addresses_start = LOOKUP_TABLE_META_SIZE
             .checked_add(old_table_addresses_len.saturating_mul(PUBKEY_BYTES))
             .ok_or(ProgramError::ArithmeticOverflow)?;
-        let new_table_data_len = LOOKUP_TABLE_META_SIZE
-            .checked_add(new_table_addresses_len.saturating_mul(PUBKEY_BYTES))
-            .ok_or(ProgramError::ArithmeticOverflow)?;
-
-        (lookup_table.meta, old_table_data_len, new_table_data_len)
+        let new_table_data_len = lookup_table_data_len(new_table_addresses_len)?;
+
+        (
+            lookup_table.meta,
+            settings.extend_hook,
+            new_addresses_start,
+            new_table_data_len,
+        )
     };
 
+    if let Some(extend_hook) = extend_hook {
+        invoke_extend_hook(&extend_hook, accounts, &new_addresses)?;
+    }
+    // Only extensions approved by the authority are recorded, so signers of
+    // open tables can't use up the table's funder slots. Their contributions
+    // are reclaimed by the recipient when the table is closed.
+    let records_funders = lookup_table_meta.records_funders()
+        && lookup_table_meta.authority == Some(*authority_info.key);
+
     AddressLookupTable::overwrite_meta_data(
         *lookup_table_info.try_borrow_mut_data()?,
         lookup_table_meta,
     )?;
 
-    lookup_table_info.realloc(new_table_data_len, false)?;
+    if new_table_data_len != lookup_table_info.data_len() {
+        lookup_table_info.realloc(new_table_data_len, false)?;
+    }
 
     {
         let mut lookup_table_data = lookup_table_info.try_borrow_mut_data()?;
         let uninitialized_addresses = AddressLookupTable::deserialize_addresses_from_index_mut(
             &mut lookup_table_data,
-            old_table_data_len,
+            new_addresses_start,
         )?;
-        uninitialized_addresses.copy_from_slice(&new_addresses);
+        uninitialized_addresses
+            .get_mut(..new_addresses.len())
+            .ok_or(ProgramError::InvalidAccountData)?
+            .copy_from_slice(&new_addresses);
     }
 
     let rent = <Rent as Sysvar>::get()?;
-    let required_lamports = rent
-        .minimum_balance(new_table_data_len)
-        .max(1)
-        .saturating_sub(lookup_table_info.lamports());
+    let required_lamports =
+        rent_exempt_balance(&rent, new_table_data_len).saturating_sub(lookup_table_info.lamports());
 
     if required_lamports > 0 {
         let payer_info = next_account_info(accounts_iter)?;
@@ -316,16 +578,291 @@ fn process_extend_lookup_table(
             &system_instruction::transfer(payer_info.key, lookup_table_info.key, required_lamports),
             &[payer_info.clone(), lookup_table_info.clone()],
         )?;
+
+        if records_funders {
+            record_lookup_table_funder(
+                program_id,
+                lookup_table_info,
+                accounts,
+                payer_info,
+                required_lamports,
+            )?;
+        }
     }
 
     Ok(())
 }
 
-fn process_deactivate_lookup_table(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+/// Funders accounts are only created at the canonical program-derived
+/// address, so they can be found without storing their bump seed.
+fn find_lookup_table_funders_address(
+    program_id: &Pubkey,
+    lookup_table_key: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[LOOKUP_TABLE_FUNDERS_SEED, lookup_table_key.as_ref()],
+        program_id,
+    )
+}
+
+/// Finds a companion account owned by the program among the instruction
+/// accounts
+fn find_companion_account_info<'a, 'b>(
+    program_id: &Pubkey,
+    companion_key: &Pubkey,
+    accounts: &'b [AccountInfo<'a>],
+) -> Result<&'b AccountInfo<'a>, ProgramError> {
+    let companion_info = accounts
+        .iter()
+        .find(|account_info| account_info.key == companion_key)
+        .ok_or_else(|| {
+            msg!("Companion account {} is missing", companion_key);
+            ProgramError::NotEnoughAccountKeys
+        })?;
+
+    if companion_info.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    Ok(companion_info)
+}
+
+/// Finds a lookup table's funders account among the instruction accounts
+fn find_lookup_table_funders_info<'a, 'b>(
+    program_id: &Pubkey,
+    lookup_table_key: &Pubkey,
+    accounts: &'b [AccountInfo<'a>],
+) -> Result<&'b AccountInfo<'a>, ProgramError> {
+    let (funders_key, _) = find_lookup_table_funders_address(program_id, lookup_table_key);
+    find_companion_account_info(program_id, &funders_key, accounts)
+}
+
+/// Settings accounts are only created at the canonical program-derived
+/// address, so they can be found without storing their bump seed.
+fn find_lookup_table_settings_address(
+    program_id: &Pubkey,
+    lookup_table_key: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[LOOKUP_TABLE_SETTINGS_SEED, lookup_table_key.as_ref()],
+        program_id,
+    )
+}
+
+/// Finds a lookup table's settings account among the instruction accounts
+fn find_lookup_table_settings_info<'a, 'b>(
+    program_id: &Pubkey,
+    lookup_table_key: &Pubkey,
+    accounts: &'b [AccountInfo<'a>],
+) -> Result<&'b AccountInfo<'a>, ProgramError> {
+    let (settings_key, _) = find_lookup_table_settings_address(program_id, lookup_table_key);
+    find_companion_account_info(program_id, &settings_key, accounts)
+}
+
+/// Loads a lookup table's settings from its settings account, which is found
+/// among the instruction accounts. Tables without a settings account have
+/// the default settings.
+fn load_lookup_table_settings(
+    program_id: &Pubkey,
+    lookup_table_key: &Pubkey,
+    lookup_table_meta: &LookupTableMeta,
+    accounts: &[AccountInfo],
+) -> Result<LookupTableSettings, ProgramError> {
+    if !lookup_table_meta.has_settings() {
+        return Ok(LookupTableSettings::new(*lookup_table_key));
+    }
+
+    let settings_info = find_lookup_table_settings_info(program_id, lookup_table_key, accounts)?;
+    LookupTableSettings::deserialize(&settings_info.try_borrow_data()?)
+}
+
+/// Creates a lookup table's settings account at its canonical
+/// program-derived address, funded by the payer
+fn create_lookup_table_settings_account<'a>(
+    program_id: &Pubkey,
+    lookup_table_key: &Pubkey,
+    settings_info: &AccountInfo<'a>,
+    payer_info: &AccountInfo<'a>,
+) -> ProgramResult {
+    if !payer_info.is_signer {
+        msg!("Payer account must be a signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let (derived_settings_key, bump_seed) =
+        find_lookup_table_settings_address(program_id, lookup_table_key);
+    if settings_info.key != &derived_settings_key {
+        msg!(
+            "Settings address must match derived address: {}",
+            derived_settings_key
+        );
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    create_program_account(
+        program_id,
+        settings_info,
+        payer_info,
+        LOOKUP_TABLE_SETTINGS_SIZE,
+        &[
+            LOOKUP_TABLE_SETTINGS_SEED,
+            lookup_table_key.as_ref(),
+            &[bump_seed],
+        ],
+    )?;
+
+    Ok(())
+}
+
+/// Records lamports contributed by a payer in a lookup table's funders
+/// account. The payer also funds any growth of the funders account, which
+/// is recorded as part of the same contribution.
+fn record_lookup_table_funder<'a>(
+    program_id: &Pubkey,
+    lookup_table_info: &AccountInfo<'a>,
+    accounts: &[AccountInfo<'a>],
+    payer_info: &AccountInfo<'a>,
+    lamports: u64,
+) -> ProgramResult {
+    let funders_info = find_lookup_table_funders_info(program_id, lookup_table_info.key, accounts)?;
+
+    let mut funders = LookupTableFunders::deserialize(&funders_info.try_borrow_data()?)?;
+    funders.record(*payer_info.key, lamports)?;
+
+    let funders_data_len = funders.serialized_size()?;
+    if funders_data_len > funders_info.data_len() {
+        funders_info.realloc(funders_data_len, false)?;
+    }
+
+    let rent = <Rent as Sysvar>::get()?;
+    let required_lamports =
+        rent_exempt_balance(&rent, funders_data_len).saturating_sub(funders_info.lamports());
+
+    if required_lamports > 0 {
+        invoke(
+            &system_instruction::transfer(payer_info.key, funders_info.key, required_lamports),
+            &[payer_info.clone(), funders_info.clone()],
+        )?;
+        funders.record(*payer_info.key, required_lamports)?;
+    }
+
+    funders.overwrite_data(*funders_info.try_borrow_mut_data()?)
+}
+
+/// Invokes a lookup table's extend hook program with the addresses about to
+/// be appended to the table. All instruction accounts are forwarded as
+/// read-only, non-signer accounts so the hook program can't act on behalf of
+/// the signers of the extension.
+fn invoke_extend_hook(
+    extend_hook: &Pubkey,
+    accounts: &[AccountInfo],
+    new_addresses: &[Pubkey],
+) -> ProgramResult {
+    if !accounts
+        .iter()
+        .any(|account_info| account_info.key == extend_hook)
+    {
+        msg!("Extend hook program {} account is missing", extend_hook);
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    let instruction = Instruction::new_with_bincode(
+        *extend_hook,
+        &ExtendHookInstruction::Execute {
+            new_addresses: new_addresses.to_vec(),
+        },
+        accounts
+            .iter()
+            .map(|account_info| AccountMeta::new_readonly(*account_info.key, false))
+            .collect(),
+    );
+
+    invoke(&instruction, accounts).inspect_err(|_| {
+        msg!(
+            "Extend hook program {} rejected the new addresses",
+            extend_hook
+        );
+    })
+}
+
+fn process_extend_from_accounts(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    num_addresses: u16,
+) -> ProgramResult {
+    // The lookup table, authority, payer, and system program accounts are
+    // followed by the accounts whose addresses will be appended. Any accounts
+    // after those, like the funders account or extend hook program account,
+    // aren't appended.
+    let new_address_accounts = accounts
+        .get(4..)
+        .and_then(|remaining_accounts| remaining_accounts.get(..num_addresses as usize))
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let new_addresses = new_address_accounts
+        .iter()
+        .map(|account_info| *account_info.key)
+        .collect();
+
+    // All accounts are passed along so they're forwarded to extend hooks.
+    process_extend_lookup_table(program_id, accounts, new_addresses, None, false)
+}
+
+fn process_copy_from_lookup_table(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    start_index: u8,
+    num_addresses: u16,
+) -> ProgramResult {
+    // The source lookup table account sits between the authority and the
+    // optional funding accounts expected when extending.
+    let source_lookup_table_info = accounts.get(2).ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    if source_lookup_table_info.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    let new_addresses = {
+        let source_lookup_table_data = source_lookup_table_info.try_borrow_data()?;
+        let source_lookup_table = AddressLookupTable::deserialize(&source_lookup_table_data)?;
+
+        let start_index = start_index as usize;
+        let end_index = start_index.saturating_add(num_addresses as usize);
+        source_lookup_table
+            .addresses
+            .get(start_index..end_index)
+            .ok_or_else(|| {
+                msg!(
+                    "Source lookup table does not contain addresses {}..{}",
+                    start_index,
+                    end_index,
+                );
+                ProgramError::from(AddressLookupError::InvalidLookupIndex)
+            })?
+            .to_vec()
+    };
+
+    let extend_accounts: Vec<AccountInfo> = accounts
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| *index != 2)
+        .map(|(_, account_info)| account_info.clone())
+        .collect();
+
+    process_extend_lookup_table(program_id, &extend_accounts, new_addresses, None, false)
+}
+
+fn process_reserve_lookup_table_capacity(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    capacity: u16,
+) -> ProgramResult {
     let accounts_iter = &mut accounts.iter();
 
     let lookup_table_info = next_account_info(accounts_iter)?;
     let authority_info = next_account_info(accounts_iter)?;
+    let payer_info = next_account_info(accounts_iter)?;
+    let _system_program_info = next_account_info(accounts_iter)?;
 
     if lookup_table_info.owner != program_id {
         return Err(ProgramError::InvalidAccountOwner);
@@ -336,11 +873,24 @@ fn process_deactivate_lookup_table(program_id: &Pubkey, accounts: &[AccountInfo]
         return Err(ProgramError::MissingRequiredSignature);
     }
 
-    let mut lookup_table_meta = {
+    if !payer_info.is_signer {
+        msg!("Payer account must be a signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    {
         let lookup_table_data = lookup_table_info.try_borrow_data()?;
         let lookup_table = AddressLookupTable::deserialize(&lookup_table_data)?;
 
-        if lookup_table.meta.authority.is_none() {
+        let settings = load_lookup_table_settings(
+            program_id,
+            lookup_table_info.key,
+            &lookup_table.meta,
+            accounts,
+        )?;
+
+        let clock = <Clock as Sysvar>::get()?;
+        if lookup_table.meta.is_frozen(&settings, clock.slot) {
             msg!("Lookup table is frozen");
             // [Core BPF]: TODO: Should be `ProgramError::Immutable`
             // See https://github.com/solana-labs/solana/pull/35113
@@ -351,31 +901,66 @@ fn process_deactivate_lookup_table(program_id: &Pubkey, accounts: &[AccountInfo]
             // See https://github.com/solana-labs/solana/pull/35113
             return Err(ProgramError::Custom(0));
         }
-        if lookup_table.meta.deactivation_slot != Slot::MAX {
-            msg!("Lookup table is already deactivated");
+        if lookup_table.meta.status(clock.slot) != LookupTableStatus::Activated {
+            msg!("Deactivated tables cannot reserve capacity");
             return Err(ProgramError::InvalidArgument);
         }
 
-        lookup_table.meta
-    };
+        if capacity as usize > LOOKUP_TABLE_MAX_ADDRESSES {
+            msg!(
+                "Lookup table capacity {} would exceed max capacity of {}",
+                capacity,
+                LOOKUP_TABLE_MAX_ADDRESSES,
+            );
+            return Err(ProgramError::InvalidInstructionData);
+        }
+    }
 
-    let clock = <Clock as Sysvar>::get()?;
-    lookup_table_meta.deactivation_slot = clock.slot;
+    // Capacity is only reserved by funding the table's rent up front. The
+    // table isn't reallocated, since the runtime would resolve any reserved
+    // address slots before they're filled.
+    let rent = <Rent as Sysvar>::get()?;
+    let required_lamports = rent_exempt_balance(&rent, lookup_table_data_len(capacity as usize)?)
+        .saturating_sub(lookup_table_info.lamports());
 
-    AddressLookupTable::overwrite_meta_data(
-        *lookup_table_info.try_borrow_mut_data()?,
-        lookup_table_meta,
-    )?;
+    if required_lamports == 0 {
+        msg!("Lookup table is already funded for {} addresses", capacity);
+        return Err(ProgramError::InvalidInstructionData);
+    }
 
-    Ok(())
+    invoke(
+        &system_instruction::transfer(payer_info.key, lookup_table_info.key, required_lamports),
+        &[payer_info.clone(), lookup_table_info.clone()],
+    )
 }
 
-fn process_close_lookup_table(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+/// Applies `update` to a lookup table's metadata and settings after checking
+/// that the signing authority can update the table. Tables with a settings
+/// account require it among the instruction accounts. If `update` changes
+/// the settings of a table without a settings account, the settings account
+/// at index 2 is created and funded by the payer at index 3.
+fn update_lookup_table<F>(program_id: &Pubkey, accounts: &[AccountInfo], update: F) -> ProgramResult
+where
+    F: FnOnce(Slot, &mut AddressLookupTable, &mut LookupTableSettings) -> ProgramResult,
+{
+    update_lookup_table_with(program_id, accounts, false, update)
+}
+
+/// Same as `update_lookup_table`, except that tables frozen until a slot can
+/// also be updated if `allow_frozen_until` is set.
+fn update_lookup_table_with<F>(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    allow_frozen_until: bool,
+    update: F,
+) -> ProgramResult
+where
+    F: FnOnce(Slot, &mut AddressLookupTable, &mut LookupTableSettings) -> ProgramResult,
+{
     let accounts_iter = &mut accounts.iter();
 
     let lookup_table_info = next_account_info(accounts_iter)?;
     let authority_info = next_account_info(accounts_iter)?;
-    let recipient_info = next_account_info(accounts_iter)?;
 
     if lookup_table_info.owner != program_id {
         return Err(ProgramError::InvalidAccountOwner);
@@ -386,23 +971,23 @@ fn process_close_lookup_table(program_id: &Pubkey, accounts: &[AccountInfo]) ->
         return Err(ProgramError::MissingRequiredSignature);
     }
 
-    // [Core BPF]: Here the legacy built-in version of ALT fallibly checks to
-    // ensure the number of instruction accounts is 3.
-    // It also checks that the recipient account is not the same as the lookup
-    // table account.
-    // The built-in does this by specifically checking the account keys at
-    // their respective indices in the instruction context.
-    // In BPF, we can just compare the addresses directly.
-    if lookup_table_info.key == recipient_info.key {
-        msg!("Lookup table cannot be the recipient of reclaimed lamports");
-        return Err(ProgramError::InvalidArgument);
-    }
-
-    {
+    let (mut lookup_table_meta, settings, settings_changed) = {
         let lookup_table_data = lookup_table_info.try_borrow_data()?;
-        let lookup_table = AddressLookupTable::deserialize(&lookup_table_data)?;
+        let mut lookup_table = AddressLookupTable::deserialize(&lookup_table_data)?;
+        let mut settings = load_lookup_table_settings(
+            program_id,
+            lookup_table_info.key,
+            &lookup_table.meta,
+            accounts,
+        )?;
 
-        if lookup_table.meta.authority.is_none() {
+        let clock = <Clock as Sysvar>::get()?;
+        let is_frozen = if allow_frozen_until {
+            lookup_table.meta.authority.is_none()
+        } else {
+            lookup_table.meta.is_frozen(&settings, clock.slot)
+        };
+        if is_frozen {
             msg!("Lookup table is frozen");
             // [Core BPF]: TODO: Should be `ProgramError::Immutable`
             // See https://github.com/solana-labs/solana/pull/35113
@@ -414,14 +999,361 @@ fn process_close_lookup_table(program_id: &Pubkey, accounts: &[AccountInfo]) ->
             return Err(ProgramError::Custom(0));
         }
 
-        let clock = <Clock as Sysvar>::get()?;
+        let old_settings = settings.clone();
+        update(clock.slot, &mut lookup_table, &mut settings)?;
+        let settings_changed = settings != old_settings;
+        (lookup_table.meta, settings, settings_changed)
+    };
 
-        // [Core BPF]: Again, since the `SlotHashes` sysvar is not available to
-        // BPF programs, we can't use the `SlotHashes` sysvar to check the
-        // status of a lookup table.
-        // Again we instead use the `Clock` sysvar here.
-        // This will no longer consider skipped slots wherein a block was not
-        // produced.
+    if settings_changed {
+        let settings_info = if lookup_table_meta.has_settings() {
+            find_lookup_table_settings_info(program_id, lookup_table_info.key, accounts)?
+        } else {
+            let settings_info = next_account_info(accounts_iter)?;
+            let payer_info = next_account_info(accounts_iter)?;
+            create_lookup_table_settings_account(
+                program_id,
+                lookup_table_info.key,
+                settings_info,
+                payer_info,
+            )?;
+            lookup_table_meta.set_has_settings();
+            settings_info
+        };
+        settings.overwrite_data(*settings_info.try_borrow_mut_data()?)?;
+    }
+
+    AddressLookupTable::overwrite_meta_data(
+        *lookup_table_info.try_borrow_mut_data()?,
+        lookup_table_meta,
+    )?;
+
+    Ok(())
+}
+
+fn process_make_lookup_table_append_only(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    update_lookup_table(program_id, accounts, |_, lookup_table, settings| {
+        // Tables scheduled for deactivation can't be made append-only, since
+        // they would deactivate regardless.
+        if lookup_table.meta.deactivation_slot != Slot::MAX
+            || settings.scheduled_deactivation_slot.is_some()
+        {
+            msg!("Deactivated tables cannot be made append-only");
+            return Err(ProgramError::InvalidArgument);
+        }
+        if lookup_table.meta.is_append_only() {
+            msg!("Lookup table is already append-only");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        lookup_table.meta.set_append_only();
+        Ok(())
+    })
+}
+
+fn process_set_lookup_table_extend_mode(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    extend_mode: LookupTableExtendMode,
+) -> ProgramResult {
+    update_lookup_table(program_id, accounts, |current_slot, lookup_table, _| {
+        if lookup_table.meta.status(current_slot) != LookupTableStatus::Activated {
+            msg!("Deactivated tables cannot change extend mode");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        lookup_table.meta.set_extend_mode(extend_mode);
+        Ok(())
+    })
+}
+
+fn process_set_lookup_table_extend_hook(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    extend_hook: Option<Pubkey>,
+) -> ProgramResult {
+    update_lookup_table(
+        program_id,
+        accounts,
+        |current_slot, lookup_table, settings| {
+            if lookup_table.meta.status(current_slot) != LookupTableStatus::Activated {
+                msg!("Deactivated tables cannot change extend hook");
+                return Err(ProgramError::InvalidArgument);
+            }
+
+            settings.extend_hook = extend_hook;
+            Ok(())
+        },
+    )
+}
+
+fn process_set_lookup_table_successor(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    successor: Pubkey,
+) -> ProgramResult {
+    let lookup_table_info = accounts.first().ok_or(ProgramError::NotEnoughAccountKeys)?;
+    if lookup_table_info.key == &successor {
+        msg!("Lookup table cannot be its own successor");
+        return Err(AddressLookupError::InvalidSuccessor.into());
+    }
+
+    // Tables frozen until a slot can still record a successor, since it
+    // doesn't change the table's addresses.
+    update_lookup_table_with(
+        program_id,
+        accounts,
+        true,
+        |current_slot, lookup_table, settings| {
+            if lookup_table.meta.deactivation_slot == Slot::MAX
+                && settings.scheduled_deactivation_slot.is_none()
+                && !lookup_table.meta.is_frozen(settings, current_slot)
+            {
+                msg!("Only deactivating or frozen tables can record a successor");
+                return Err(ProgramError::InvalidArgument);
+            }
+
+            settings.successor = Some(successor);
+            Ok(())
+        },
+    )
+}
+
+fn process_freeze_lookup_table_with_successor(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    successor: Pubkey,
+) -> ProgramResult {
+    let lookup_table_info = accounts.first().ok_or(ProgramError::NotEnoughAccountKeys)?;
+    if lookup_table_info.key == &successor {
+        msg!("Lookup table cannot be its own successor");
+        return Err(AddressLookupError::InvalidSuccessor.into());
+    }
+
+    // The successor is recorded while the authority can still update the
+    // table, since permanently frozen tables no longer have an authority.
+    update_lookup_table(program_id, accounts, |_, _, settings| {
+        settings.successor = Some(successor);
+        Ok(())
+    })?;
+    process_freeze_lookup_table(program_id, accounts, None)
+}
+
+fn process_deactivate_lookup_table(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let lookup_table_info = next_account_info(accounts_iter)?;
+    let authority_info = next_account_info(accounts_iter)?;
+
+    if lookup_table_info.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    if !authority_info.is_signer {
+        msg!("Authority account must be a signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let clock = <Clock as Sysvar>::get()?;
+    let mut lookup_table_meta = {
+        let lookup_table_data = lookup_table_info.try_borrow_data()?;
+        let lookup_table = AddressLookupTable::deserialize(&lookup_table_data)?;
+        let settings = load_lookup_table_settings(
+            program_id,
+            lookup_table_info.key,
+            &lookup_table.meta,
+            accounts,
+        )?;
+
+        if lookup_table.meta.is_frozen(&settings, clock.slot) {
+            msg!("Lookup table is frozen");
+            // [Core BPF]: TODO: Should be `ProgramError::Immutable`
+            // See https://github.com/solana-labs/solana/pull/35113
+            return Err(ProgramError::Custom(0));
+        }
+        if lookup_table.meta.authority != Some(*authority_info.key) {
+            // [Core BPF]: TODO: Should be `ProgramError::IncorrectAuthority`
+            // See https://github.com/solana-labs/solana/pull/35113
+            return Err(ProgramError::Custom(0));
+        }
+        if lookup_table.meta.is_append_only() {
+            msg!("Append-only tables cannot be deactivated");
+            return Err(AddressLookupError::AppendOnly.into());
+        }
+        // Tables with a scheduled deactivation can still be deactivated
+        // immediately, which leaves nothing for the schedule to apply to.
+        if lookup_table.meta.deactivation_slot != Slot::MAX {
+            msg!("Lookup table is already deactivated");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        lookup_table.meta
+    };
+
+    lookup_table_meta.deactivation_slot = clock.slot;
+
+    AddressLookupTable::overwrite_meta_data(
+        *lookup_table_info.try_borrow_mut_data()?,
+        lookup_table_meta,
+    )?;
+
+    Ok(())
+}
+
+fn process_deactivate_lookup_table_at_slot(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    deactivation_slot: Slot,
+) -> ProgramResult {
+    let clock = <Clock as Sysvar>::get()?;
+    if deactivation_slot < clock.slot {
+        msg!(
+            "Deactivation slot {} is before the current slot {}",
+            deactivation_slot,
+            clock.slot,
+        );
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    if deactivation_slot == clock.slot {
+        return process_deactivate_lookup_table(program_id, accounts);
+    }
+
+    // The runtime treats any deactivation slot other than `Slot::MAX` as a
+    // deactivation, so the table stays activated and the slot is stored in
+    // its settings until `DeactivateScheduledLookupTable` applies it.
+    update_lookup_table(program_id, accounts, |_, lookup_table, settings| {
+        if lookup_table.meta.is_append_only() {
+            msg!("Append-only tables cannot be deactivated");
+            return Err(AddressLookupError::AppendOnly.into());
+        }
+        if lookup_table.meta.deactivation_slot != Slot::MAX {
+            msg!("Lookup table is already deactivated");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        settings.scheduled_deactivation_slot = Some(deactivation_slot);
+        Ok(())
+    })
+}
+
+fn process_deactivate_scheduled_lookup_table(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let lookup_table_info = accounts.first().ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    if lookup_table_info.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    let mut lookup_table_meta = {
+        let lookup_table_data = lookup_table_info.try_borrow_data()?;
+        AddressLookupTable::deserialize(&lookup_table_data)?.meta
+    };
+    if !lookup_table_meta.has_settings() {
+        msg!("Lookup table has no scheduled deactivation");
+        return Err(ProgramError::InvalidArgument);
+    }
+    let settings_info =
+        find_lookup_table_settings_info(program_id, lookup_table_info.key, accounts)?;
+    let mut settings = LookupTableSettings::deserialize(&settings_info.try_borrow_data()?)?;
+
+    let clock = <Clock as Sysvar>::get()?;
+    let Some(scheduled_deactivation_slot) = settings.scheduled_deactivation_slot else {
+        msg!("Lookup table has no scheduled deactivation");
+        return Err(ProgramError::InvalidArgument);
+    };
+    if clock.slot < scheduled_deactivation_slot {
+        msg!(
+            "Scheduled deactivation slot {} has not been reached",
+            scheduled_deactivation_slot,
+        );
+        return Err(ProgramError::InvalidArgument);
+    }
+    if lookup_table_meta.deactivation_slot != Slot::MAX {
+        msg!("Lookup table is already deactivated");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // The table is deactivated in the current slot rather than the scheduled
+    // slot, so that it still goes through the full cool-down period.
+    lookup_table_meta.deactivation_slot = clock.slot;
+    settings.scheduled_deactivation_slot = None;
+
+    settings.overwrite_data(*settings_info.try_borrow_mut_data()?)?;
+    AddressLookupTable::overwrite_meta_data(
+        *lookup_table_info.try_borrow_mut_data()?,
+        lookup_table_meta,
+    )?;
+
+    Ok(())
+}
+
+fn process_close_lookup_table(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let lookup_table_info = next_account_info(accounts_iter)?;
+    let authority_info = next_account_info(accounts_iter)?;
+    let recipient_info = next_account_info(accounts_iter)?;
+
+    if lookup_table_info.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    if !authority_info.is_signer {
+        msg!("Authority account must be a signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // [Core BPF]: Here the legacy built-in version of ALT fallibly checks to
+    // ensure the number of instruction accounts is 3.
+    // It also checks that the recipient account is not the same as the lookup
+    // table account.
+    // The built-in does this by specifically checking the account keys at
+    // their respective indices in the instruction context.
+    // In BPF, we can just compare the addresses directly.
+    if lookup_table_info.key == recipient_info.key {
+        msg!("Lookup table cannot be the recipient of reclaimed lamports");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let (closes_settings, records_funders, is_registered) = {
+        let lookup_table_data = lookup_table_info.try_borrow_data()?;
+        let lookup_table = AddressLookupTable::deserialize(&lookup_table_data)?;
+
+        let settings = load_lookup_table_settings(
+            program_id,
+            lookup_table_info.key,
+            &lookup_table.meta,
+            accounts,
+        )?;
+
+        let clock = <Clock as Sysvar>::get()?;
+        if lookup_table.meta.is_frozen(&settings, clock.slot) {
+            msg!("Lookup table is frozen");
+            // [Core BPF]: TODO: Should be `ProgramError::Immutable`
+            // See https://github.com/solana-labs/solana/pull/35113
+            return Err(ProgramError::Custom(0));
+        }
+        if lookup_table.meta.authority != Some(*authority_info.key) {
+            // [Core BPF]: TODO: Should be `ProgramError::IncorrectAuthority`
+            // See https://github.com/solana-labs/solana/pull/35113
+            return Err(ProgramError::Custom(0));
+        }
+        if lookup_table.meta.is_append_only() {
+            msg!("Append-only tables cannot be closed");
+            return Err(AddressLookupError::AppendOnly.into());
+        }
+
+        // [Core BPF]: Again, since the `SlotHashes` sysvar is not available to
+        // BPF programs, we can't use the `SlotHashes` sysvar to check the
+        // status of a lookup table.
+        // Again we instead use the `Clock` sysvar here.
+        // This will no longer consider skipped slots wherein a block was not
+        // produced.
         // See `state::LookupTableMeta::status` for more details.
         match lookup_table.meta.status(clock.slot) {
             LookupTableStatus::Activated => {
@@ -437,18 +1369,742 @@ fn process_close_lookup_table(program_id: &Pubkey, accounts: &[AccountInfo]) ->
             }
             LookupTableStatus::Deactivated => Ok(()),
         }?;
+
+        // Settings accounts which record a successor outlive the table, so
+        // that the successor can still be resolved from the table address.
+        (
+            lookup_table.meta.has_settings() && settings.successor.is_none(),
+            lookup_table.meta.records_funders(),
+            lookup_table.meta.is_registered(),
+        )
+    };
+
+    let mut reclaimed_lamports = lookup_table_info.lamports();
+    **lookup_table_info.try_borrow_mut_lamports()? = 0;
+
+    if closes_settings {
+        let settings_info =
+            find_lookup_table_settings_info(program_id, lookup_table_info.key, accounts)?;
+        reclaimed_lamports = reclaimed_lamports
+            .checked_add(settings_info.lamports())
+            .ok_or::<ProgramError>(ProgramError::ArithmeticOverflow)?;
+        **settings_info.try_borrow_mut_lamports()? = 0;
+        settings_info.realloc(0, true)?;
+    }
+
+    if records_funders {
+        reclaimed_lamports = refund_lookup_table_funders(
+            program_id,
+            lookup_table_info,
+            accounts,
+            reclaimed_lamports,
+        )?;
+    }
+
+    if is_registered {
+        unregister_lookup_table(
+            program_id,
+            lookup_table_info.key,
+            authority_info.key,
+            accounts,
+        )?;
+    }
+
+    let new_recipient_lamports = reclaimed_lamports
+        .checked_add(recipient_info.lamports())
+        .ok_or::<ProgramError>(ProgramError::ArithmeticOverflow)?;
+
+    **recipient_info.try_borrow_mut_lamports()? = new_recipient_lamports;
+
+    // Lookup tables are _not_ reassigned when closed.
+    lookup_table_info.realloc(0, true)?;
+
+    Ok(())
+}
+
+/// Closes a lookup table's funders account and refunds each recorded funder
+/// from the combined lamports of the closed table and its funders account.
+/// Funders whose accounts are missing, or can't receive their refund, aren't
+/// refunded so that they can't prevent the table from being closed.
+/// Returns the lamports which weren't refunded.
+fn refund_lookup_table_funders<'a>(
+    program_id: &Pubkey,
+    lookup_table_info: &AccountInfo<'a>,
+    accounts: &[AccountInfo<'a>],
+    lookup_table_lamports: u64,
+) -> Result<u64, ProgramError> {
+    let funders_info = find_lookup_table_funders_info(program_id, lookup_table_info.key, accounts)?;
+
+    let funders = LookupTableFunders::deserialize(&funders_info.try_borrow_data()?)?;
+    let available_lamports = lookup_table_lamports
+        .checked_add(funders_info.lamports())
+        .ok_or::<ProgramError>(ProgramError::ArithmeticOverflow)?;
+    let (refunds, mut remaining_lamports) = funders.refunds(available_lamports)?;
+
+    **funders_info.try_borrow_mut_lamports()? = 0;
+    funders_info.realloc(0, true)?;
+
+    let rent = <Rent as Sysvar>::get()?;
+    for refund in refunds {
+        let new_funder_lamports = accounts
+            .iter()
+            .find(|account_info| account_info.key == &refund.address)
+            .filter(|funder_info| funder_info.is_writable && !funder_info.executable)
+            .and_then(|funder_info| {
+                let new_funder_lamports = funder_info.lamports().checked_add(refund.lamports)?;
+                // Crediting an account that isn't left rent exempt, like a
+                // drained funder, would fail the whole transaction.
+                rent.is_exempt(new_funder_lamports, funder_info.data_len())
+                    .then_some((funder_info, new_funder_lamports))
+            });
+
+        match new_funder_lamports {
+            Some((funder_info, new_funder_lamports)) => {
+                **funder_info.try_borrow_mut_lamports()? = new_funder_lamports;
+            }
+            None => {
+                msg!("Funder {} can't be refunded", refund.address);
+                remaining_lamports = remaining_lamports
+                    .checked_add(refund.lamports)
+                    .ok_or::<ProgramError>(ProgramError::ArithmeticOverflow)?;
+            }
+        }
+    }
+
+    Ok(remaining_lamports)
+}
+
+/// Loads the lookup table registry of an authority
+fn load_lookup_table_registry(
+    program_id: &Pubkey,
+    registry_info: &AccountInfo,
+    authority_key: &Pubkey,
+) -> Result<LookupTableRegistry, ProgramError> {
+    if registry_info.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    let registry = LookupTableRegistry::deserialize(&registry_info.try_borrow_data()?)?;
+    if registry.authority != *authority_key {
+        msg!("Registry account does not belong to the authority");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    Ok(registry)
+}
+
+/// Writes an updated lookup table registry, reallocating the registry
+/// account as needed. Any additional lamports required to cover the
+/// rent-exempt balance are transferred from the payer.
+fn overwrite_lookup_table_registry<'a>(
+    registry_info: &AccountInfo<'a>,
+    registry: &LookupTableRegistry,
+    payer_info: Option<&AccountInfo<'a>>,
+) -> ProgramResult {
+    let registry_data_len = registry.serialized_size()?;
+    if registry_data_len != registry_info.data_len() {
+        registry_info.realloc(registry_data_len, false)?;
+    }
+    registry.overwrite_data(*registry_info.try_borrow_mut_data()?)?;
+
+    let rent = <Rent as Sysvar>::get()?;
+    let required_lamports =
+        rent_exempt_balance(&rent, registry_data_len).saturating_sub(registry_info.lamports());
+
+    if required_lamports > 0 {
+        let payer_info = payer_info.ok_or(ProgramError::NotEnoughAccountKeys)?;
+        invoke(
+            &system_instruction::transfer(payer_info.key, registry_info.key, required_lamports),
+            &[payer_info.clone(), registry_info.clone()],
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Registry accounts are only created at the canonical program-derived
+/// address, so they can be found without storing their bump seed.
+fn find_lookup_table_registry_address(program_id: &Pubkey, authority_key: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[LOOKUP_TABLE_REGISTRY_SEED, authority_key.as_ref()],
+        program_id,
+    )
+}
+
+/// Removes a lookup table from its authority's registry, which is found
+/// among the instruction accounts
+fn unregister_lookup_table(
+    program_id: &Pubkey,
+    lookup_table_key: &Pubkey,
+    authority_key: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let (registry_key, _) = find_lookup_table_registry_address(program_id, authority_key);
+    let registry_info = accounts
+        .iter()
+        .find(|account_info| account_info.key == &registry_key)
+        .ok_or_else(|| {
+            msg!("Lookup table registry account {} is missing", registry_key);
+            ProgramError::NotEnoughAccountKeys
+        })?;
+
+    let mut registry = load_lookup_table_registry(program_id, registry_info, authority_key)?;
+    registry.unregister(lookup_table_key);
+    overwrite_lookup_table_registry(registry_info, &registry, None)
+}
+
+fn process_deactivate_lookup_tables(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    num_lookup_tables: u8,
+) -> ProgramResult {
+    let (authority_info, remaining_accounts) = accounts
+        .split_first()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let (lookup_table_infos, companion_infos) =
+        split_lookup_table_accounts(remaining_accounts, num_lookup_tables)?;
+
+    for (index, lookup_table_info) in (0..=u8::MAX).zip(lookup_table_infos) {
+        let mut table_accounts = vec![lookup_table_info.clone(), authority_info.clone()];
+        table_accounts.extend_from_slice(companion_infos);
+
+        process_deactivate_lookup_table(program_id, &table_accounts).map_err(|err| {
+            msg!(
+                "Failed to deactivate lookup table {} at index {}: {}",
+                lookup_table_info.key,
+                index,
+                err,
+            );
+            lookup_tables_error(index, err)
+        })?;
+    }
+
+    Ok(())
+}
+
+fn process_close_lookup_tables(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    num_lookup_tables: u8,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let authority_info = next_account_info(accounts_iter)?;
+    let recipient_info = next_account_info(accounts_iter)?;
+    let (lookup_table_infos, companion_infos) =
+        split_lookup_table_accounts(accounts_iter.as_slice(), num_lookup_tables)?;
+
+    for (index, lookup_table_info) in (0..=u8::MAX).zip(lookup_table_infos) {
+        let mut table_accounts = vec![
+            lookup_table_info.clone(),
+            authority_info.clone(),
+            recipient_info.clone(),
+        ];
+        table_accounts.extend_from_slice(companion_infos);
+
+        process_close_lookup_table(program_id, &table_accounts).map_err(|err| {
+            msg!(
+                "Failed to close lookup table {} at index {}: {}",
+                lookup_table_info.key,
+                index,
+                err,
+            );
+            lookup_tables_error(index, err)
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Splits the accounts of an instruction which processes multiple lookup
+/// tables into the lookup table accounts and the companion accounts which
+/// follow them
+fn split_lookup_table_accounts<'a, 'b>(
+    accounts: &'b [AccountInfo<'a>],
+    num_lookup_tables: u8,
+) -> Result<(&'b [AccountInfo<'a>], &'b [AccountInfo<'a>]), ProgramError> {
+    if num_lookup_tables == 0 {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    let num_lookup_tables = num_lookup_tables as usize;
+    match (
+        accounts.get(..num_lookup_tables),
+        accounts.get(num_lookup_tables..),
+    ) {
+        (Some(lookup_table_infos), Some(companion_infos)) => {
+            Ok((lookup_table_infos, companion_infos))
+        }
+        _ => Err(ProgramError::NotEnoughAccountKeys),
+    }
+}
+
+fn process_initialize_lookup_table_funders(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    bump_seed: u8,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let lookup_table_info = next_account_info(accounts_iter)?;
+    let _authority_info = next_account_info(accounts_iter)?;
+    let funders_info = next_account_info(accounts_iter)?;
+    let payer_info = next_account_info(accounts_iter)?;
+
+    if !payer_info.is_signer {
+        msg!("Payer account must be a signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let (derived_funders_key, canonical_bump_seed) =
+        find_lookup_table_funders_address(program_id, lookup_table_info.key);
+    if funders_info.key != &derived_funders_key || bump_seed != canonical_bump_seed {
+        msg!(
+            "Funders address must match derived address: {}",
+            derived_funders_key
+        );
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    update_lookup_table(program_id, accounts, |current_slot, lookup_table, _| {
+        if lookup_table.meta.status(current_slot) != LookupTableStatus::Activated {
+            msg!("Deactivated tables cannot record funders");
+            return Err(ProgramError::InvalidArgument);
+        }
+        if lookup_table.meta.records_funders() {
+            msg!("Lookup table already records its funders");
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        lookup_table.meta.set_records_funders();
+        Ok(())
+    })?;
+
+    // The payer's contribution to the funders account's own rent is recorded
+    // so that it's refunded when the table is closed. The recorded lamports
+    // don't affect the account's size.
+    let mut funders = LookupTableFunders::new(*lookup_table_info.key);
+    funders.record(*payer_info.key, 0)?;
+    let required_lamports = create_program_account(
+        program_id,
+        funders_info,
+        payer_info,
+        funders.serialized_size()?,
+        &[
+            LOOKUP_TABLE_FUNDERS_SEED,
+            lookup_table_info.key.as_ref(),
+            &[bump_seed],
+        ],
+    )?;
+    funders.record(*payer_info.key, required_lamports)?;
+
+    funders.overwrite_data(*funders_info.try_borrow_mut_data()?)
+}
+
+/// Allocates and assigns a program-derived account to the program, after
+/// funding its rent-exempt balance from the payer. Returns the lamports
+/// transferred from the payer.
+fn create_program_account<'a>(
+    program_id: &Pubkey,
+    account_info: &AccountInfo<'a>,
+    payer_info: &AccountInfo<'a>,
+    data_len: usize,
+    signer_seeds: &[&[u8]],
+) -> Result<u64, ProgramError> {
+    let rent = <Rent as Sysvar>::get()?;
+    let required_lamports =
+        rent_exempt_balance(&rent, data_len).saturating_sub(account_info.lamports());
+
+    if required_lamports > 0 {
+        invoke(
+            &system_instruction::transfer(payer_info.key, account_info.key, required_lamports),
+            &[payer_info.clone(), account_info.clone()],
+        )?;
+    }
+
+    invoke_signed(
+        &system_instruction::allocate(account_info.key, data_len as u64),
+        std::slice::from_ref(account_info),
+        &[signer_seeds],
+    )?;
+
+    invoke_signed(
+        &system_instruction::assign(account_info.key, program_id),
+        std::slice::from_ref(account_info),
+        &[signer_seeds],
+    )?;
+
+    Ok(required_lamports)
+}
+
+/// Checks that the authority can change a lookup table's labels, which
+/// follows the same rules as extending the table with its authority.
+/// Returns the number of addresses stored in the table.
+fn check_lookup_table_labels_authority(
+    program_id: &Pubkey,
+    lookup_table_info: &AccountInfo,
+    authority_info: &AccountInfo,
+    accounts: &[AccountInfo],
+) -> Result<usize, ProgramError> {
+    if lookup_table_info.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    if !authority_info.is_signer {
+        msg!("Authority account must be a signer");
+        return Err(ProgramError::MissingRequiredSignature);
     }
 
-    let new_recipient_lamports = lookup_table_info
+    let lookup_table_data = lookup_table_info.try_borrow_data()?;
+    let lookup_table = AddressLookupTable::deserialize(&lookup_table_data)?;
+
+    let settings = load_lookup_table_settings(
+        program_id,
+        lookup_table_info.key,
+        &lookup_table.meta,
+        accounts,
+    )?;
+
+    let clock = <Clock as Sysvar>::get()?;
+    if lookup_table.meta.is_frozen(&settings, clock.slot) {
+        msg!("Lookup table is frozen");
+        // [Core BPF]: TODO: Should be `ProgramError::Immutable`
+        // See https://github.com/solana-labs/solana/pull/35113
+        return Err(ProgramError::Custom(0));
+    }
+    if lookup_table.meta.authority != Some(*authority_info.key) {
+        // [Core BPF]: TODO: Should be `ProgramError::IncorrectAuthority`
+        // See https://github.com/solana-labs/solana/pull/35113
+        return Err(ProgramError::Custom(0));
+    }
+    if lookup_table.meta.status(clock.slot) != LookupTableStatus::Activated {
+        msg!("Deactivated tables cannot be labeled");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    Ok(lookup_table.addresses.len())
+}
+
+fn process_initialize_lookup_table_labels(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    bump_seed: u8,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let lookup_table_info = next_account_info(accounts_iter)?;
+    let authority_info = next_account_info(accounts_iter)?;
+    let labels_info = next_account_info(accounts_iter)?;
+    let payer_info = next_account_info(accounts_iter)?;
+
+    if !payer_info.is_signer {
+        msg!("Payer account must be a signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    check_lookup_table_labels_authority(program_id, lookup_table_info, authority_info, accounts)?;
+
+    let derived_labels_key = Pubkey::create_program_address(
+        &[
+            LOOKUP_TABLE_LABELS_SEED,
+            lookup_table_info.key.as_ref(),
+            &[bump_seed],
+        ],
+        program_id,
+    )?;
+    if labels_info.key != &derived_labels_key {
+        msg!(
+            "Labels address must match derived address: {}",
+            derived_labels_key
+        );
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if check_id(labels_info.owner) {
+        msg!("Lookup table labels already exist");
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    let labels = LookupTableLabels::new(*lookup_table_info.key, *authority_info.key);
+    create_program_account(
+        program_id,
+        labels_info,
+        payer_info,
+        labels.serialized_size()?,
+        &[
+            LOOKUP_TABLE_LABELS_SEED,
+            lookup_table_info.key.as_ref(),
+            &[bump_seed],
+        ],
+    )?;
+
+    labels.overwrite_data(*labels_info.try_borrow_mut_data()?)
+}
+
+/// Applies `update` to a lookup table's labels, reallocating the labels
+/// account as needed. Any additional lamports required to cover the
+/// rent-exempt balance are transferred from the optional payer account, and
+/// any lamports freed by shrinking the labels account are returned to it.
+fn process_update_lookup_table_labels<F>(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    update: F,
+) -> ProgramResult
+where
+    F: FnOnce(usize, &mut LookupTableLabels) -> ProgramResult,
+{
+    let accounts_iter = &mut accounts.iter();
+
+    let lookup_table_info = next_account_info(accounts_iter)?;
+    let authority_info = next_account_info(accounts_iter)?;
+    let labels_info = next_account_info(accounts_iter)?;
+
+    let addresses_len = check_lookup_table_labels_authority(
+        program_id,
+        lookup_table_info,
+        authority_info,
+        accounts,
+    )?;
+
+    if labels_info.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    let mut labels = LookupTableLabels::deserialize(&labels_info.try_borrow_data()?)?;
+    if labels.lookup_table != *lookup_table_info.key {
+        msg!("Labels account does not belong to the lookup table");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    update(addresses_len, &mut labels)?;
+
+    if labels.description.len() > LOOKUP_TABLE_MAX_DESCRIPTION_LEN {
+        msg!(
+            "Description length {} exceeds max length of {}",
+            labels.description.len(),
+            LOOKUP_TABLE_MAX_DESCRIPTION_LEN,
+        );
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    if let Some(label) = labels
+        .labels
+        .iter()
+        .find(|label| label.label.len() > LOOKUP_TABLE_MAX_LABEL_LEN)
+    {
+        msg!(
+            "Label length {} exceeds max length of {}",
+            label.label.len(),
+            LOOKUP_TABLE_MAX_LABEL_LEN,
+        );
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let labels_data_len = labels.serialized_size()?;
+    if labels_data_len != labels_info.data_len() {
+        labels_info.realloc(labels_data_len, false)?;
+    }
+    labels.overwrite_data(*labels_info.try_borrow_mut_data()?)?;
+
+    let rent = <Rent as Sysvar>::get()?;
+    let minimum_lamports = rent_exempt_balance(&rent, labels_data_len);
+    let required_lamports = minimum_lamports.saturating_sub(labels_info.lamports());
+    let excess_lamports = labels_info.lamports().saturating_sub(minimum_lamports);
+
+    if required_lamports > 0 {
+        let payer_info = next_account_info(accounts_iter)?;
+        let _system_program_info = next_account_info(accounts_iter)?;
+
+        if !payer_info.is_signer {
+            msg!("Payer account must be a signer");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        invoke(
+            &system_instruction::transfer(payer_info.key, labels_info.key, required_lamports),
+            &[payer_info.clone(), labels_info.clone()],
+        )?;
+    } else if let Some(recipient_info) = accounts_iter.next().filter(|_| excess_lamports > 0) {
+        if recipient_info.key == labels_info.key {
+            msg!("Labels account cannot be the recipient of freed lamports");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let new_recipient_lamports = recipient_info
+            .lamports()
+            .checked_add(excess_lamports)
+            .ok_or::<ProgramError>(ProgramError::ArithmeticOverflow)?;
+
+        **labels_info.try_borrow_mut_lamports()? = minimum_lamports;
+        **recipient_info.try_borrow_mut_lamports()? = new_recipient_lamports;
+    }
+
+    Ok(())
+}
+
+fn process_close_lookup_table_labels(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let labels_info = next_account_info(accounts_iter)?;
+    let authority_info = next_account_info(accounts_iter)?;
+    let recipient_info = next_account_info(accounts_iter)?;
+
+    if labels_info.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    if !authority_info.is_signer {
+        msg!("Authority account must be a signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if labels_info.key == recipient_info.key {
+        msg!("Labels account cannot be the recipient of reclaimed lamports");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Labels are closed by the authority which created them, since the
+    // lookup table may already be frozen or closed.
+    let labels = LookupTableLabels::deserialize(&labels_info.try_borrow_data()?)?;
+    if labels.authority != *authority_info.key {
+        // [Core BPF]: TODO: Should be `ProgramError::IncorrectAuthority`
+        // See https://github.com/solana-labs/solana/pull/35113
+        return Err(ProgramError::Custom(0));
+    }
+
+    let new_recipient_lamports = labels_info
         .lamports()
         .checked_add(recipient_info.lamports())
         .ok_or::<ProgramError>(ProgramError::ArithmeticOverflow)?;
 
-    **lookup_table_info.try_borrow_mut_lamports()? = 0;
+    **labels_info.try_borrow_mut_lamports()? = 0;
     **recipient_info.try_borrow_mut_lamports()? = new_recipient_lamports;
+    labels_info.realloc(0, true)?;
 
-    // Lookup tables are _not_ reassigned when closed.
-    lookup_table_info.realloc(0, true)?;
+    Ok(())
+}
+
+fn process_initialize_lookup_table_registry(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    bump_seed: u8,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let registry_info = next_account_info(accounts_iter)?;
+    let authority_info = next_account_info(accounts_iter)?;
+    let payer_info = next_account_info(accounts_iter)?;
+
+    if !authority_info.is_signer {
+        msg!("Authority account must be a signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if !payer_info.is_signer {
+        msg!("Payer account must be a signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let (derived_registry_key, canonical_bump_seed) =
+        find_lookup_table_registry_address(program_id, authority_info.key);
+    if registry_info.key != &derived_registry_key || bump_seed != canonical_bump_seed {
+        msg!(
+            "Registry address must match derived address: {}",
+            derived_registry_key
+        );
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if check_id(registry_info.owner) {
+        msg!("Lookup table registry already exists");
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    let registry = LookupTableRegistry::new(*authority_info.key, bump_seed);
+    create_program_account(
+        program_id,
+        registry_info,
+        payer_info,
+        registry.serialized_size()?,
+        &[
+            LOOKUP_TABLE_REGISTRY_SEED,
+            authority_info.key.as_ref(),
+            &[bump_seed],
+        ],
+    )?;
+
+    registry.overwrite_data(*registry_info.try_borrow_mut_data()?)
+}
+
+fn process_withdraw_excess_lamports(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let lookup_table_info = next_account_info(accounts_iter)?;
+    let authority_info = next_account_info(accounts_iter)?;
+    let recipient_info = next_account_info(accounts_iter)?;
+
+    if lookup_table_info.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    if !authority_info.is_signer {
+        msg!("Authority account must be a signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if lookup_table_info.key == recipient_info.key {
+        msg!("Lookup table cannot be the recipient of withdrawn lamports");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    {
+        let lookup_table_data = lookup_table_info.try_borrow_data()?;
+        let lookup_table = AddressLookupTable::deserialize(&lookup_table_data)?;
+
+        let settings = load_lookup_table_settings(
+            program_id,
+            lookup_table_info.key,
+            &lookup_table.meta,
+            accounts,
+        )?;
+
+        let clock = <Clock as Sysvar>::get()?;
+        if lookup_table.meta.is_frozen(&settings, clock.slot) {
+            msg!("Lookup table is frozen");
+            // [Core BPF]: TODO: Should be `ProgramError::Immutable`
+            // See https://github.com/solana-labs/solana/pull/35113
+            return Err(ProgramError::Custom(0));
+        }
+        if lookup_table.meta.authority != Some(*authority_info.key) {
+            // [Core BPF]: TODO: Should be `ProgramError::IncorrectAuthority`
+            // See https://github.com/solana-labs/solana/pull/35113
+            return Err(ProgramError::Custom(0));
+        }
+    }
+
+    let rent = <Rent as Sysvar>::get()?;
+    let minimum_lamports = rent_exempt_balance(&rent, lookup_table_info.data_len());
+    let excess_lamports = lookup_table_info
+        .lamports()
+        .saturating_sub(minimum_lamports);
+
+    if excess_lamports == 0 {
+        msg!("Lookup table has no lamports in excess of its rent-exempt balance");
+        return Err(ProgramError::InsufficientFunds);
+    }
+
+    let new_recipient_lamports = recipient_info
+        .lamports()
+        .checked_add(excess_lamports)
+        .ok_or::<ProgramError>(ProgramError::ArithmeticOverflow)?;
+
+    **lookup_table_info.try_borrow_mut_lamports()? = minimum_lamports;
+    **recipient_info.try_borrow_mut_lamports()? = new_recipient_lamports;
 
     Ok(())
 }
@@ -463,15 +2119,23 @@ pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], input: &[u8]) -> P
             bump_seed,
         } => {
             msg!("Instruction: CreateLookupTable");
-            process_create_lookup_table(program_id, accounts, recent_slot, bump_seed)
+            process_create_lookup_table(
+                program_id,
+                accounts,
+                recent_slot,
+                bump_seed,
+                vec![],
+                None,
+                NewLookupTableOptions::default(),
+            )
         }
         ProgramInstruction::FreezeLookupTable => {
             msg!("Instruction: FreezeLookupTable");
-            process_freeze_lookup_table(program_id, accounts)
+            process_freeze_lookup_table(program_id, accounts, None)
         }
         ProgramInstruction::ExtendLookupTable { new_addresses } => {
             msg!("Instruction: ExtendLookupTable");
-            process_extend_lookup_table(program_id, accounts, new_addresses)
+            process_extend_lookup_table(program_id, accounts, new_addresses, None, false)
         }
         ProgramInstruction::DeactivateLookupTable => {
             msg!("Instruction: DeactivateLookupTable");
@@ -481,5 +2145,224 @@ pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], input: &[u8]) -> P
             msg!("Instruction: CloseLookupTable");
             process_close_lookup_table(program_id, accounts)
         }
+        ProgramInstruction::CreateLookupTableWithAddresses {
+            recent_slot,
+            bump_seed,
+            addresses,
+        } => {
+            msg!("Instruction: CreateLookupTableWithAddresses");
+            if addresses.is_empty() {
+                msg!("Must create with at least one address");
+                return Err(ProgramError::InvalidInstructionData);
+            }
+            // Fail rather than silently ignore the addresses if the table
+            // already exists.
+            process_create_lookup_table(
+                program_id,
+                accounts,
+                recent_slot,
+                bump_seed,
+                addresses,
+                Some(CreateLookupTableMode::Strict),
+                NewLookupTableOptions::default(),
+            )
+        }
+        ProgramInstruction::ReserveLookupTableCapacity { capacity } => {
+            msg!("Instruction: ReserveLookupTableCapacity");
+            process_reserve_lookup_table_capacity(program_id, accounts, capacity)
+        }
+        ProgramInstruction::WithdrawExcessLamports => {
+            msg!("Instruction: WithdrawExcessLamports");
+            process_withdraw_excess_lamports(program_id, accounts)
+        }
+        ProgramInstruction::CreateLookupTableChecked {
+            recent_slot,
+            bump_seed,
+            mode,
+        } => {
+            msg!("Instruction: CreateLookupTableChecked");
+            process_create_lookup_table(
+                program_id,
+                accounts,
+                recent_slot,
+                bump_seed,
+                vec![],
+                Some(mode),
+                NewLookupTableOptions::default(),
+            )
+        }
+        ProgramInstruction::ExtendLookupTableChecked {
+            new_addresses,
+            expected_start_index,
+        } => {
+            msg!("Instruction: ExtendLookupTableChecked");
+            process_extend_lookup_table(
+                program_id,
+                accounts,
+                new_addresses,
+                Some(expected_start_index),
+                false,
+            )
+        }
+        ProgramInstruction::FreezeLookupTableChecked {
+            expected_addresses_len,
+            expected_addresses_hash,
+        } => {
+            msg!("Instruction: FreezeLookupTableChecked");
+            process_freeze_lookup_table(
+                program_id,
+                accounts,
+                Some((expected_addresses_len, expected_addresses_hash)),
+            )
+        }
+        ProgramInstruction::ExtendLookupTableIdempotent {
+            new_addresses,
+            expected_start_index,
+        } => {
+            msg!("Instruction: ExtendLookupTableIdempotent");
+            process_extend_lookup_table(
+                program_id,
+                accounts,
+                new_addresses,
+                Some(expected_start_index),
+                true,
+            )
+        }
+        ProgramInstruction::ExtendFromAccounts { num_addresses } => {
+            msg!("Instruction: ExtendFromAccounts");
+            process_extend_from_accounts(program_id, accounts, num_addresses)
+        }
+        ProgramInstruction::CopyFromLookupTable {
+            start_index,
+            num_addresses,
+        } => {
+            msg!("Instruction: CopyFromLookupTable");
+            process_copy_from_lookup_table(program_id, accounts, start_index, num_addresses)
+        }
+        ProgramInstruction::DeactivateLookupTables { num_lookup_tables } => {
+            msg!("Instruction: DeactivateLookupTables");
+            process_deactivate_lookup_tables(program_id, accounts, num_lookup_tables)
+        }
+        ProgramInstruction::CloseLookupTables { num_lookup_tables } => {
+            msg!("Instruction: CloseLookupTables");
+            process_close_lookup_tables(program_id, accounts, num_lookup_tables)
+        }
+        ProgramInstruction::DeactivateLookupTableAtSlot { deactivation_slot } => {
+            msg!("Instruction: DeactivateLookupTableAtSlot");
+            process_deactivate_lookup_table_at_slot(program_id, accounts, deactivation_slot)
+        }
+        ProgramInstruction::CreateLookupTableWithExpiry {
+            recent_slot,
+            bump_seed,
+            deactivation_slot,
+        } => {
+            msg!("Instruction: CreateLookupTableWithExpiry");
+            process_create_lookup_table(
+                program_id,
+                accounts,
+                recent_slot,
+                bump_seed,
+                vec![],
+                Some(CreateLookupTableMode::Strict),
+                NewLookupTableOptions {
+                    deactivation_slot: Some(deactivation_slot),
+                    ..NewLookupTableOptions::default()
+                },
+            )
+        }
+        ProgramInstruction::MakeLookupTableAppendOnly => {
+            msg!("Instruction: MakeLookupTableAppendOnly");
+            process_make_lookup_table_append_only(program_id, accounts)
+        }
+        ProgramInstruction::SetLookupTableExtendMode { extend_mode } => {
+            msg!("Instruction: SetLookupTableExtendMode");
+            process_set_lookup_table_extend_mode(program_id, accounts, extend_mode)
+        }
+        ProgramInstruction::SetLookupTableExtendHook { extend_hook } => {
+            msg!("Instruction: SetLookupTableExtendHook");
+            process_set_lookup_table_extend_hook(program_id, accounts, extend_hook)
+        }
+        ProgramInstruction::CreateLookupTableWithExtendHook {
+            recent_slot,
+            bump_seed,
+            extend_hook,
+        } => {
+            msg!("Instruction: CreateLookupTableWithExtendHook");
+            process_create_lookup_table(
+                program_id,
+                accounts,
+                recent_slot,
+                bump_seed,
+                vec![],
+                Some(CreateLookupTableMode::Strict),
+                NewLookupTableOptions {
+                    extend_hook: Some(extend_hook),
+                    ..NewLookupTableOptions::default()
+                },
+            )
+        }
+        ProgramInstruction::InitializeLookupTableFunders { bump_seed } => {
+            msg!("Instruction: InitializeLookupTableFunders");
+            process_initialize_lookup_table_funders(program_id, accounts, bump_seed)
+        }
+        ProgramInstruction::FreezeLookupTableUntil { slot } => {
+            msg!("Instruction: FreezeLookupTableUntil");
+            process_freeze_lookup_table_until(program_id, accounts, slot)
+        }
+        ProgramInstruction::InitializeLookupTableLabels { bump_seed } => {
+            msg!("Instruction: InitializeLookupTableLabels");
+            process_initialize_lookup_table_labels(program_id, accounts, bump_seed)
+        }
+        ProgramInstruction::SetLookupTableLabel { index, label } => {
+            msg!("Instruction: SetLookupTableLabel");
+            process_update_lookup_table_labels(program_id, accounts, |addresses_len, labels| {
+                if index as usize >= addresses_len {
+                    msg!(
+                        "Lookup table does not contain an address at index {}",
+                        index
+                    );
+                    return Err(AddressLookupError::InvalidLookupIndex.into());
+                }
+                labels.set_label(index, label);
+                Ok(())
+            })
+        }
+        ProgramInstruction::ClearLookupTableLabel { index } => {
+            msg!("Instruction: ClearLookupTableLabel");
+            process_update_lookup_table_labels(program_id, accounts, |_, labels| {
+                if !labels.clear_label(index) {
+                    msg!("Lookup table index {} has no label", index);
+                    return Err(ProgramError::InvalidArgument);
+                }
+                Ok(())
+            })
+        }
+        ProgramInstruction::SetLookupTableDescription { description } => {
+            msg!("Instruction: SetLookupTableDescription");
+            process_update_lookup_table_labels(program_id, accounts, |_, labels| {
+                labels.description = description;
+                Ok(())
+            })
+        }
+        ProgramInstruction::InitializeLookupTableRegistry { bump_seed } => {
+            msg!("Instruction: InitializeLookupTableRegistry");
+            process_initialize_lookup_table_registry(program_id, accounts, bump_seed)
+        }
+        ProgramInstruction::DeactivateScheduledLookupTable => {
+            msg!("Instruction: DeactivateScheduledLookupTable");
+            process_deactivate_scheduled_lookup_table(program_id, accounts)
+        }
+        ProgramInstruction::SetLookupTableSuccessor { successor } => {
+            msg!("Instruction: SetLookupTableSuccessor");
+            process_set_lookup_table_successor(program_id, accounts, successor)
+        }
+        ProgramInstruction::CloseLookupTableLabels => {
+            msg!("Instruction: CloseLookupTableLabels");
+            process_close_lookup_table_labels(program_id, accounts)
+        }
+        ProgramInstruction::FreezeLookupTableWithSuccessor { successor } => {
+            msg!("Instruction: FreezeLookupTableWithSuccessor");
+            process_freeze_lookup_table_with_successor(program_id, accounts, successor)
+        }
     }
 }
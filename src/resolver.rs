@@ -0,0 +1,217 @@
+//! Off-chain resolution of retired lookup tables to their successors
+
+use {
+    crate::{
+        error::AddressLookupError,
+        instruction::derive_lookup_table_settings_address,
+        state::{AddressLookupTable, LookupTableSettings},
+        AddressLookupTableAccount,
+    },
+    solana_program::{clock::Slot, pubkey::Pubkey},
+};
+
+/// Resolves a lookup table to the newest active table in its successor chain.
+///
+/// Successors are recorded in each table's settings account, which outlives
+/// the table once it's closed, and are followed until a table without a
+/// successor, or a table account which no longer exists and has no
+/// successor, is reached. `fetch_account` returns the owner and data of an
+/// account, or `None` if the account doesn't exist.
+pub fn resolve_lookup_table_account<F>(
+    lookup_table_address: &Pubkey,
+    current_slot: Slot,
+    mut fetch_account: F,
+) -> Result<AddressLookupTableAccount, AddressLookupError>
+where
+    F: FnMut(&Pubkey) -> Option<(Pubkey, Vec<u8>)>,
+{
+    let mut visited_addresses = Vec::new();
+    let mut next_address = Some(*lookup_table_address);
+    let mut newest_active_table = None;
+
+    while let Some(address) = next_address {
+        if visited_addresses.contains(&address) {
+            return Err(AddressLookupError::InvalidSuccessor);
+        }
+        visited_addresses.push(address);
+
+        let (settings_address, _) = derive_lookup_table_settings_address(&address);
+        let Some(data) = fetch_program_account(&mut fetch_account, &address)? else {
+            // Closed tables keep their settings account if it records a
+            // successor.
+            next_address = fetch_program_account(&mut fetch_account, &settings_address)?
+                .map(|settings_data| deserialize_successor(&settings_data))
+                .transpose()?
+                .flatten();
+            continue;
+        };
+        let lookup_table = AddressLookupTable::deserialize(&data)
+            .map_err(|_| AddressLookupError::InvalidAccountData)?;
+
+        if lookup_table.meta.is_active(current_slot) {
+            newest_active_table = Some(AddressLookupTableAccount {
+                key: address,
+                addresses: lookup_table.addresses.to_vec(),
+            });
+        }
+        next_address = if lookup_table.meta.has_settings() {
+            let settings_data = fetch_program_account(&mut fetch_account, &settings_address)?
+                .ok_or(AddressLookupError::InvalidAccountData)?;
+            deserialize_successor(&settings_data)?
+        } else {
+            None
+        };
+    }
+
+    newest_active_table.ok_or(AddressLookupError::LookupTableAccountNotFound)
+}
+
+fn deserialize_successor(settings_data: &[u8]) -> Result<Option<Pubkey>, AddressLookupError> {
+    LookupTableSettings::deserialize(settings_data)
+        .map(|settings| settings.successor)
+        .map_err(|_| AddressLookupError::InvalidAccountData)
+}
+
+/// Fetches the data of an account owned by the program, or `None` if the
+/// account doesn't exist
+fn fetch_program_account<F>(
+    fetch_account: &mut F,
+    address: &Pubkey,
+) -> Result<Option<Vec<u8>>, AddressLookupError>
+where
+    F: FnMut(&Pubkey) -> Option<(Pubkey, Vec<u8>)>,
+{
+    match fetch_account(address) {
+        Some((owner, _)) if !crate::check_id(&owner) => {
+            Err(AddressLookupError::InvalidAccountOwner)
+        }
+        account => Ok(account.map(|(_, data)| data)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::state::{LookupTableMeta, LOOKUP_TABLE_SETTINGS_SIZE},
+        std::{borrow::Cow, collections::HashMap},
+    };
+
+    fn serialize_table(deactivation_slot: Slot) -> Vec<u8> {
+        let mut meta = LookupTableMeta {
+            deactivation_slot,
+            ..LookupTableMeta::new(Pubkey::new_unique())
+        };
+        meta.set_has_settings();
+        AddressLookupTable {
+            meta,
+            addresses: Cow::Owned(vec![Pubkey::new_unique()]),
+        }
+        .serialize_for_tests()
+        .unwrap()
+    }
+
+    /// Adds a table account along with its settings account
+    fn insert_table(
+        accounts: &mut HashMap<Pubkey, Vec<u8>>,
+        address: Pubkey,
+        deactivation_slot: Slot,
+        successor: Option<Pubkey>,
+    ) {
+        let settings = LookupTableSettings {
+            successor,
+            ..LookupTableSettings::new(address)
+        };
+        let mut settings_data = vec![0; LOOKUP_TABLE_SETTINGS_SIZE];
+        settings.overwrite_data(&mut settings_data).unwrap();
+        let (settings_address, _) = derive_lookup_table_settings_address(&address);
+        accounts.insert(settings_address, settings_data);
+        accounts.insert(address, serialize_table(deactivation_slot));
+    }
+
+    fn resolve(
+        accounts: &HashMap<Pubkey, Vec<u8>>,
+        lookup_table_address: &Pubkey,
+    ) -> Result<Pubkey, AddressLookupError> {
+        resolve_lookup_table_account(lookup_table_address, 1_000, |address| {
+            accounts
+                .get(address)
+                .map(|data| (crate::id(), data.clone()))
+        })
+        .map(|lookup_table_account| lookup_table_account.key)
+    }
+
+    #[test]
+    fn test_resolve_lookup_table_account() {
+        let [retired, retiring, newest] = [(); 3].map(|_| Pubkey::new_unique());
+        let mut accounts = HashMap::new();
+        insert_table(&mut accounts, retired, 0, Some(retiring));
+        insert_table(&mut accounts, retiring, 1_000, Some(newest));
+        insert_table(&mut accounts, newest, Slot::MAX, None);
+
+        assert_eq!(resolve(&accounts, &retired), Ok(newest));
+        assert_eq!(resolve(&accounts, &retiring), Ok(newest));
+        assert_eq!(resolve(&accounts, &newest), Ok(newest));
+
+        // The newest active table is used when the successor doesn't exist
+        accounts.remove(&newest);
+        assert_eq!(resolve(&accounts, &retired), Ok(retiring));
+
+        // Fully deactivated tables are skipped
+        insert_table(&mut accounts, retiring, 0, Some(newest));
+        assert_eq!(
+            resolve(&accounts, &retired),
+            Err(AddressLookupError::LookupTableAccountNotFound)
+        );
+    }
+
+    #[test]
+    fn test_resolve_closed_lookup_table_account() {
+        let [closed, newest] = [(); 2].map(|_| Pubkey::new_unique());
+        let mut accounts = HashMap::new();
+        insert_table(&mut accounts, closed, 0, Some(newest));
+        insert_table(&mut accounts, newest, Slot::MAX, None);
+
+        // The settings account outlives the closed table
+        accounts.remove(&closed);
+        assert_eq!(resolve(&accounts, &closed), Ok(newest));
+    }
+
+    #[test]
+    fn test_resolve_lookup_table_account_with_cycle() {
+        let [table_a, table_b] = [(); 2].map(|_| Pubkey::new_unique());
+        let mut accounts = HashMap::new();
+        insert_table(&mut accounts, table_a, 0, Some(table_b));
+        insert_table(&mut accounts, table_b, 0, Some(table_a));
+
+        assert_eq!(
+            resolve(&accounts, &table_a),
+            Err(AddressLookupError::InvalidSuccessor)
+        );
+    }
+
+    #[test]
+    fn test_resolve_lookup_table_account_with_invalid_owner() {
+        let lookup_table_address = Pubkey::new_unique();
+        let data = serialize_table(Slot::MAX);
+
+        assert_eq!(
+            resolve_lookup_table_account(&lookup_table_address, 1_000, |_| Some((
+                Pubkey::new_unique(),
+                data.clone()
+            ))),
+            Err(AddressLookupError::InvalidAccountOwner)
+        );
+    }
+
+    #[test]
+    fn test_resolve_lookup_table_account_without_settings_account() {
+        let lookup_table_address = Pubkey::new_unique();
+        let accounts = HashMap::from([(lookup_table_address, serialize_table(Slot::MAX))]);
+
+        assert_eq!(
+            resolve(&accounts, &lookup_table_address),
+            Err(AddressLookupError::InvalidAccountData)
+        );
+    }
+}
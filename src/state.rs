@@ -1,9 +1,19 @@
+#[cfg(not(target_os = "solana"))]
+use solana_program::message::{
+    v0::{LoadedAddresses, MessageAddressTableLookup},
+    AddressLoaderError,
+};
 use {
     crate::error::AddressLookupError,
     serde::{Deserialize, Serialize},
     solana_frozen_abi_macro::{AbiEnumVisitor, AbiExample},
     solana_program::{
-        clock::Slot, program_error::ProgramError, pubkey::Pubkey, slot_hashes::MAX_ENTRIES,
+        clock::Slot,
+        hash::{hashv, Hash},
+        program_error::ProgramError,
+        pubkey::{Pubkey, PUBKEY_BYTES},
+        rent::Rent,
+        slot_hashes::MAX_ENTRIES,
     },
     std::borrow::Cow,
 };
@@ -14,6 +24,69 @@ pub const LOOKUP_TABLE_MAX_ADDRESSES: usize = 256;
 /// The serialized size of lookup table metadata
 pub const LOOKUP_TABLE_META_SIZE: usize = 56;
 
+/// Seed used to derive the address of a lookup table's funders account
+pub const LOOKUP_TABLE_FUNDERS_SEED: &[u8] = b"funders";
+
+/// The maximum number of funders a lookup table's funders account records
+pub const LOOKUP_TABLE_MAX_FUNDERS: usize = 16;
+
+/// Seed used to derive the address of a lookup table's labels account
+pub const LOOKUP_TABLE_LABELS_SEED: &[u8] = b"labels";
+
+/// Seed used to derive the address of an authority's lookup table registry
+pub const LOOKUP_TABLE_REGISTRY_SEED: &[u8] = b"registry";
+
+/// Seed used to derive the address of a lookup table's settings account
+pub const LOOKUP_TABLE_SETTINGS_SEED: &[u8] = b"settings";
+
+/// The account data length of a lookup table's settings account. Unused
+/// bytes are kept zeroed so that new settings can be added without
+/// reallocating existing accounts.
+pub const LOOKUP_TABLE_SETTINGS_SIZE: usize = 128;
+
+/// The maximum length in bytes of a lookup table address label
+pub const LOOKUP_TABLE_MAX_LABEL_LEN: usize = 32;
+
+/// The maximum length in bytes of a lookup table description
+pub const LOOKUP_TABLE_MAX_DESCRIPTION_LEN: usize = 256;
+
+/// Returns the minimum balance of a program account storing `data_len` bytes.
+/// Accounts are kept rent-exempt and always hold at least one lamport.
+pub fn rent_exempt_balance(rent: &Rent, data_len: usize) -> u64 {
+    rent.minimum_balance(data_len).max(1)
+}
+
+/// Returns the account data length of a lookup table which stores
+/// `addresses_len` addresses
+pub fn lookup_table_data_len(addresses_len: usize) -> Result<usize, ProgramError> {
+    addresses_len
+        .checked_mul(PUBKEY_BYTES)
+        .and_then(|addresses_data_len| addresses_data_len.checked_add(LOOKUP_TABLE_META_SIZE))
+        .ok_or(ProgramError::ArithmeticOverflow)
+}
+
+/// Returns the rent-exempt balance of a lookup table which stores
+/// `addresses_len` addresses
+pub fn lookup_table_rent_exempt_balance(
+    rent: &Rent,
+    addresses_len: usize,
+) -> Result<u64, ProgramError> {
+    lookup_table_data_len(addresses_len).map(|data_len| rent_exempt_balance(rent, data_len))
+}
+
+/// Returns the additional lamports required to keep a rent-exempt lookup
+/// table rent-exempt after extending it from `old_addresses_len` to
+/// `new_addresses_len` addresses
+pub fn lookup_table_extension_lamports(
+    rent: &Rent,
+    old_addresses_len: usize,
+    new_addresses_len: usize,
+) -> Result<u64, ProgramError> {
+    let old_balance = lookup_table_rent_exempt_balance(rent, old_addresses_len)?;
+    let new_balance = lookup_table_rent_exempt_balance(rent, new_addresses_len)?;
+    Ok(new_balance.saturating_sub(old_balance))
+}
+
 // [Core BPF]: Newly-implemented logic for calculating slot position relative
 // to the current slot on the `Clock`.
 fn calculate_slot_position(target_slot: &Slot, current_slot: &Slot) -> Option<usize> {
@@ -29,10 +102,40 @@ fn calculate_slot_position(target_slot: &Slot, current_slot: &Slot) -> Option<us
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum LookupTableStatus {
     Activated,
-    Deactivating { remaining_blocks: usize },
+    // [Core BPF]: Since status is calculated from the `Clock` rather than the
+    // `SlotHashes` sysvar, remaining blocks are counted in slots, including
+    // slots where no block was produced.
+    /// The table can be closed once `remaining_blocks` more slots have
+    /// passed, except in its deactivation slot, where `remaining_blocks` is
+    /// one more than the slots until it can be closed.
+    Deactivating {
+        remaining_blocks: usize,
+    },
     Deactivated,
 }
 
+/// Lookup status of the address stored at a lookup table index
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum LookupIndexStatus {
+    /// The address can be looked up.
+    Active,
+    /// The address was appended in the current slot, so lookups fail with
+    /// `AddressLookupError::InvalidLookupIndex` until `active_slot`.
+    Pending { active_slot: Slot },
+}
+
+/// Authority state of a lookup table
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum LookupTableAuthorityState {
+    /// The table can be modified by its authority.
+    Mutable { authority: Pubkey },
+    /// The table behaves as frozen until the slot is reached, after which
+    /// its authority regains control.
+    FrozenUntil { authority: Pubkey, slot: Slot },
+    /// The table is permanently frozen.
+    Frozen,
+}
+
 /// Address lookup table metadata
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, AbiExample)]
 pub struct LookupTableMeta {
@@ -49,12 +152,29 @@ pub struct LookupTableMeta {
     pub last_extended_slot_start_index: u8,
     /// Authority address which must sign for each modification.
     pub authority: Option<Pubkey>,
-    // Padding to keep addresses 8-byte aligned
+    // Padding to keep addresses 8-byte aligned. Since the runtime ignores
+    // it, the program stores flags for table settings here.
     pub _padding: u16,
     // Raw list of addresses follows this serialized structure in
     // the account's data, starting from `LOOKUP_TABLE_META_SIZE`.
 }
 
+/// Flag set in `LookupTableMeta::_padding` for append-only tables
+const APPEND_ONLY_FLAG: u16 = 1 << 9;
+/// Bits of `LookupTableMeta::_padding` which store the table's
+/// `LookupTableExtendMode`
+const EXTEND_MODE_SHIFT: u32 = 10;
+const EXTEND_MODE_MASK: u16 = 0b11 << EXTEND_MODE_SHIFT;
+/// Flag set in `LookupTableMeta::_padding` for tables which record their
+/// funders in a companion `LookupTableFunders` account
+const RECORDS_FUNDERS_FLAG: u16 = 1 << 12;
+/// Flag set in `LookupTableMeta::_padding` for tables listed in their
+/// authority's `LookupTableRegistry` account
+const REGISTERED_FLAG: u16 = 1 << 13;
+/// Flag set in `LookupTableMeta::_padding` for tables with a companion
+/// `LookupTableSettings` account
+const HAS_SETTINGS_FLAG: u16 = 1 << 14;
+
 impl Default for LookupTableMeta {
     fn default() -> Self {
         Self {
@@ -75,6 +195,115 @@ impl LookupTableMeta {
         }
     }
 
+    /// Returns whether the table can never be deactivated or closed
+    pub fn is_append_only(&self) -> bool {
+        self._padding & APPEND_ONLY_FLAG != 0
+    }
+
+    /// Permanently marks the table as append-only
+    pub fn set_append_only(&mut self) {
+        self._padding |= APPEND_ONLY_FLAG;
+    }
+
+    /// Returns whether the table has a companion `LookupTableSettings`
+    /// account
+    pub fn has_settings(&self) -> bool {
+        self._padding & HAS_SETTINGS_FLAG != 0
+    }
+
+    /// Marks the table as having a companion `LookupTableSettings` account
+    pub fn set_has_settings(&mut self) {
+        self._padding |= HAS_SETTINGS_FLAG;
+    }
+
+    /// Returns whether the table records its funders in a companion
+    /// `LookupTableFunders` account
+    pub fn records_funders(&self) -> bool {
+        self._padding & RECORDS_FUNDERS_FLAG != 0
+    }
+
+    /// Marks the table as recording its funders
+    pub fn set_records_funders(&mut self) {
+        self._padding |= RECORDS_FUNDERS_FLAG;
+    }
+
+    /// Returns whether the table is listed in its authority's registry
+    pub fn is_registered(&self) -> bool {
+        self._padding & REGISTERED_FLAG != 0
+    }
+
+    /// Sets whether the table is listed in its authority's registry
+    pub fn set_registered(&mut self, registered: bool) {
+        if registered {
+            self._padding |= REGISTERED_FLAG;
+        } else {
+            self._padding &= !REGISTERED_FLAG;
+        }
+    }
+
+    /// Returns the current authority state of the lookup table with the
+    /// given settings
+    pub fn authority_state(
+        &self,
+        settings: &LookupTableSettings,
+        current_slot: Slot,
+    ) -> LookupTableAuthorityState {
+        let Some(authority) = self.authority else {
+            return LookupTableAuthorityState::Frozen;
+        };
+        match settings.frozen_until {
+            Some(slot) if current_slot < slot => {
+                LookupTableAuthorityState::FrozenUntil { authority, slot }
+            }
+            _ => LookupTableAuthorityState::Mutable { authority },
+        }
+    }
+
+    /// Returns whether the table currently behaves as frozen, either
+    /// permanently or until a future slot
+    pub fn is_frozen(&self, settings: &LookupTableSettings, current_slot: Slot) -> bool {
+        !matches!(
+            self.authority_state(settings, current_slot),
+            LookupTableAuthorityState::Mutable { .. }
+        )
+    }
+
+    /// Returns which signers are allowed to extend the table
+    pub fn extend_mode(&self) -> LookupTableExtendMode {
+        match (self._padding & EXTEND_MODE_MASK) >> EXTEND_MODE_SHIFT {
+            1 => LookupTableExtendMode::Open,
+            2 => LookupTableExtendMode::OpenDeduplicated,
+            _ => LookupTableExtendMode::Authority,
+        }
+    }
+
+    /// Sets which signers are allowed to extend the table
+    pub fn set_extend_mode(&mut self, extend_mode: LookupTableExtendMode) {
+        let bits = match extend_mode {
+            LookupTableExtendMode::Authority => 0,
+            LookupTableExtendMode::Open => 1,
+            LookupTableExtendMode::OpenDeduplicated => 2,
+        };
+        self._padding = (self._padding & !EXTEND_MODE_MASK) | (bits << EXTEND_MODE_SHIFT);
+    }
+
+    /// Returns the first slot at which the table is fully deactivated and can
+    /// be closed, if the table has been deactivated
+    pub fn closable_at_slot(&self) -> Option<Slot> {
+        if self.deactivation_slot == Slot::MAX {
+            None
+        } else {
+            Some(self.deactivation_slot.saturating_add(MAX_ENTRIES as Slot))
+        }
+    }
+
+    /// Returns the number of slots until the table can be closed, which is
+    /// zero once the table is fully deactivated
+    pub fn slots_until_closable(&self, current_slot: Slot) -> Option<Slot> {
+        self.closable_at_slot()
+            .map(|closable_slot| closable_slot.saturating_sub(current_slot))
+    }
+
     /// Returns whether the table is considered active for address lookups
     pub fn is_active(&self, current_slot: Slot) -> bool {
         match self.status(current_slot) {
@@ -123,6 +352,21 @@ impl LookupTableMeta {
     }
 }
 
+/// Determines which signers are allowed to extend a lookup table
+#[derive(
+    Debug, Default, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, AbiExample, AbiEnumVisitor,
+)]
+pub enum LookupTableExtendMode {
+    /// Only the table authority can extend the table.
+    #[default]
+    Authority,
+    /// Any signer can extend the table, funding any additional rent.
+    Open,
+    /// Any signer can extend the table, funding any additional rent. New
+    /// addresses which are already stored in the table are skipped.
+    OpenDeduplicated,
+}
+
 /// Program account states
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, AbiExample, AbiEnumVisitor)]
 #[allow(clippy::large_enum_variant)]
@@ -131,6 +375,14 @@ pub enum ProgramState {
     Uninitialized,
     /// Initialized `LookupTable` account.
     LookupTable(LookupTableMeta),
+    /// Initialized companion account recording the funders of a lookup table.
+    LookupTableFunders(LookupTableFunders),
+    /// Initialized companion account labeling the addresses of a lookup table.
+    LookupTableLabels(LookupTableLabels),
+    /// Initialized account listing the lookup tables of an authority.
+    LookupTableRegistry(LookupTableRegistry),
+    /// Initialized companion account storing the settings of a lookup table.
+    LookupTableSettings(LookupTableSettings),
 }
 
 impl ProgramState {
@@ -148,6 +400,353 @@ impl ProgramState {
     }
 }
 
+/// Lamports contributed by a single payer towards a lookup table's rent
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq, Clone, AbiExample)]
+pub struct LookupTableFunder {
+    pub address: Pubkey,
+    pub lamports: u64,
+}
+
+/// Funders of a lookup table, stored in a companion account derived from the
+/// table address. Funders are refunded pro rata when the table is closed.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq, Clone, AbiExample)]
+pub struct LookupTableFunders {
+    /// The lookup table whose funders are recorded.
+    pub lookup_table: Pubkey,
+    /// Contributions in the order their funders were first recorded.
+    pub funders: Vec<LookupTableFunder>,
+}
+
+impl LookupTableFunders {
+    pub fn new(lookup_table: Pubkey) -> Self {
+        Self {
+            lookup_table,
+            funders: Vec::new(),
+        }
+    }
+
+    /// Deserialize the funders recorded in a companion account
+    pub fn deserialize(data: &[u8]) -> Result<Self, ProgramError> {
+        match bincode::deserialize(data).map_err(|_| ProgramError::InvalidAccountData)? {
+            ProgramState::LookupTableFunders(funders) => Ok(funders),
+            ProgramState::Uninitialized => Err(ProgramError::UninitializedAccount),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+
+    /// Serialize the recorded funders into companion account data and zero
+    /// any leftover bytes.
+    pub fn overwrite_data(&self, data: &mut [u8]) -> Result<(), ProgramError> {
+        data.fill(0);
+        bincode::serialize_into(data, &ProgramState::LookupTableFunders(self.clone()))
+            .map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    /// Returns the account data length required to store the recorded funders
+    pub fn serialized_size(&self) -> Result<usize, ProgramError> {
+        bincode::serialized_size(&ProgramState::LookupTableFunders(self.clone()))
+            .map(|size| size as usize)
+            .map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    /// Add lamports to a funder's recorded contribution, recording the
+    /// funder first if needed. New funders can't be recorded once
+    /// `LOOKUP_TABLE_MAX_FUNDERS` funders are recorded.
+    pub fn record(&mut self, address: Pubkey, lamports: u64) -> Result<(), ProgramError> {
+        match self
+            .funders
+            .iter_mut()
+            .find(|funder| funder.address == address)
+        {
+            Some(funder) => {
+                funder.lamports = funder
+                    .lamports
+                    .checked_add(lamports)
+                    .ok_or(ProgramError::ArithmeticOverflow)?;
+            }
+            None => {
+                if self.funders.len() >= LOOKUP_TABLE_MAX_FUNDERS {
+                    return Err(AddressLookupError::TooManyFunders.into());
+                }
+                self.funders.push(LookupTableFunder { address, lamports });
+            }
+        }
+        Ok(())
+    }
+
+    /// Split `available_lamports` between the recorded funders. Each funder
+    /// is refunded at most their contribution, and funders are refunded pro
+    /// rata if the available lamports don't cover every contribution. Any
+    /// lamports which aren't refunded are returned as the remainder.
+    pub fn refunds(
+        &self,
+        available_lamports: u64,
+    ) -> Result<(Vec<LookupTableFunder>, u64), ProgramError> {
+        let total_lamports = self
+            .funders
+            .iter()
+            .try_fold(0u64, |total, funder| total.checked_add(funder.lamports))
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        let refunds = self
+            .funders
+            .iter()
+            .map(|funder| {
+                let lamports = if available_lamports >= total_lamports {
+                    funder.lamports
+                } else {
+                    // Can't overflow, since the result is at most
+                    // `available_lamports`
+                    ((funder.lamports as u128)
+                        .saturating_mul(available_lamports as u128)
+                        .checked_div(total_lamports as u128)
+                        .unwrap_or(0)) as u64
+                };
+                LookupTableFunder {
+                    address: funder.address,
+                    lamports,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let remainder = refunds
+            .iter()
+            .try_fold(available_lamports, |remainder, refund| {
+                remainder.checked_sub(refund.lamports)
+            })
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        Ok((refunds, remainder))
+    }
+}
+
+/// Human-readable label of the address stored at a lookup table index
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq, Clone, AbiExample)]
+pub struct LookupTableLabel {
+    pub index: u8,
+    pub label: String,
+}
+
+/// Labels of a lookup table's addresses and a description of the table,
+/// stored in a companion account derived from the table address.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq, Clone, AbiExample)]
+pub struct LookupTableLabels {
+    /// The lookup table whose addresses are labeled.
+    pub lookup_table: Pubkey,
+    /// The table authority which created the labels, which can close the
+    /// labels account even after the table is frozen or closed.
+    pub authority: Pubkey,
+    /// Description of the lookup table.
+    pub description: String,
+    /// Labels sorted by table index.
+    pub labels: Vec<LookupTableLabel>,
+}
+
+impl LookupTableLabels {
+    pub fn new(lookup_table: Pubkey, authority: Pubkey) -> Self {
+        Self {
+            lookup_table,
+            authority,
+            ..Self::default()
+        }
+    }
+
+    /// Deserialize the labels stored in a companion account
+    pub fn deserialize(data: &[u8]) -> Result<Self, ProgramError> {
+        match bincode::deserialize(data).map_err(|_| ProgramError::InvalidAccountData)? {
+            ProgramState::LookupTableLabels(labels) => Ok(labels),
+            ProgramState::Uninitialized => Err(ProgramError::UninitializedAccount),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+
+    /// Serialize the labels into companion account data and zero any
+    /// leftover bytes.
+    pub fn overwrite_data(&self, data: &mut [u8]) -> Result<(), ProgramError> {
+        data.fill(0);
+        bincode::serialize_into(data, &ProgramState::LookupTableLabels(self.clone()))
+            .map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    /// Returns the account data length required to store the labels
+    pub fn serialized_size(&self) -> Result<usize, ProgramError> {
+        bincode::serialized_size(&ProgramState::LookupTableLabels(self.clone()))
+            .map(|size| size as usize)
+            .map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    /// Returns the label of the address stored at a table index
+    pub fn label(&self, index: u8) -> Option<&str> {
+        self.labels
+            .binary_search_by_key(&index, |label| label.index)
+            .ok()
+            .map(|position| self.labels[position].label.as_str())
+    }
+
+    /// Set the label of the address stored at a table index, replacing any
+    /// existing label
+    pub fn set_label(&mut self, index: u8, label: String) {
+        match self
+            .labels
+            .binary_search_by_key(&index, |label| label.index)
+        {
+            Ok(position) => self.labels[position].label = label,
+            Err(position) => self
+                .labels
+                .insert(position, LookupTableLabel { index, label }),
+        }
+    }
+
+    /// Clear the label of the address stored at a table index, returning
+    /// whether there was a label to clear
+    pub fn clear_label(&mut self, index: u8) -> bool {
+        match self
+            .labels
+            .binary_search_by_key(&index, |label| label.index)
+        {
+            Ok(position) => {
+                self.labels.remove(position);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Join a lookup table's addresses with their labels, in table order
+    pub fn join<'a>(&'a self, addresses: &[Pubkey]) -> Vec<(Pubkey, Option<&'a str>)> {
+        addresses
+            .iter()
+            .enumerate()
+            .map(|(index, address)| {
+                let label = u8::try_from(index).ok().and_then(|index| self.label(index));
+                (*address, label)
+            })
+            .collect()
+    }
+}
+
+/// Lookup tables controlled by an authority, stored in an account derived
+/// from the authority address so that they can be enumerated without
+/// scanning every program account.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq, Clone, AbiExample)]
+pub struct LookupTableRegistry {
+    /// The authority whose lookup tables are listed.
+    pub authority: Pubkey,
+    /// Bump seed of the registry address.
+    pub bump_seed: u8,
+    /// Registered lookup tables in the order they were created.
+    pub lookup_tables: Vec<Pubkey>,
+}
+
+impl LookupTableRegistry {
+    pub fn new(authority: Pubkey, bump_seed: u8) -> Self {
+        Self {
+            authority,
+            bump_seed,
+            lookup_tables: Vec::new(),
+        }
+    }
+
+    /// Deserialize the lookup tables listed in a registry account
+    pub fn deserialize(data: &[u8]) -> Result<Self, ProgramError> {
+        match bincode::deserialize(data).map_err(|_| ProgramError::InvalidAccountData)? {
+            ProgramState::LookupTableRegistry(registry) => Ok(registry),
+            ProgramState::Uninitialized => Err(ProgramError::UninitializedAccount),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+
+    /// Serialize the registry into account data and zero any leftover bytes.
+    pub fn overwrite_data(&self, data: &mut [u8]) -> Result<(), ProgramError> {
+        data.fill(0);
+        bincode::serialize_into(data, &ProgramState::LookupTableRegistry(self.clone()))
+            .map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    /// Returns the account data length required to store the registry
+    pub fn serialized_size(&self) -> Result<usize, ProgramError> {
+        bincode::serialized_size(&ProgramState::LookupTableRegistry(self.clone()))
+            .map(|size| size as usize)
+            .map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    /// Add a lookup table to the registry, returning whether it wasn't
+    /// already registered
+    pub fn register(&mut self, lookup_table: Pubkey) -> bool {
+        if self.lookup_tables.contains(&lookup_table) {
+            return false;
+        }
+        self.lookup_tables.push(lookup_table);
+        true
+    }
+
+    /// Remove a lookup table from the registry, returning whether it was
+    /// registered
+    pub fn unregister(&mut self, lookup_table: &Pubkey) -> bool {
+        match self
+            .lookup_tables
+            .iter()
+            .position(|registered| registered == lookup_table)
+        {
+            Some(position) => {
+                self.lookup_tables.remove(position);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Settings of a lookup table which don't fit in its metadata, stored in a
+/// companion account derived from the table address. The runtime only reads
+/// the table account, so these settings are only enforced by the program.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq, Clone, AbiExample)]
+pub struct LookupTableSettings {
+    /// The lookup table whose settings are stored.
+    pub lookup_table: Pubkey,
+    /// Program invoked with new addresses before they're appended to the
+    /// table, which can reject the extension by failing.
+    pub extend_hook: Option<Pubkey>,
+    /// The table behaves as frozen until this slot is reached, after which
+    /// the stored authority regains control.
+    pub frozen_until: Option<Slot>,
+    /// Table which replaces this table once it's retired, which can only be
+    /// recorded on deactivating or frozen tables.
+    pub successor: Option<Pubkey>,
+    /// Slot at which the table may be deactivated by the permissionless
+    /// `DeactivateScheduledLookupTable` instruction. The table stays
+    /// activated until then, since the runtime treats any
+    /// `LookupTableMeta::deactivation_slot` other than `Slot::MAX` as a
+    /// deactivation.
+    pub scheduled_deactivation_slot: Option<Slot>,
+}
+
+impl LookupTableSettings {
+    pub fn new(lookup_table: Pubkey) -> Self {
+        Self {
+            lookup_table,
+            ..Self::default()
+        }
+    }
+
+    /// Deserialize the settings stored in a companion account
+    pub fn deserialize(data: &[u8]) -> Result<Self, ProgramError> {
+        match bincode::deserialize(data).map_err(|_| ProgramError::InvalidAccountData)? {
+            ProgramState::LookupTableSettings(settings) => Ok(settings),
+            ProgramState::Uninitialized => Err(ProgramError::UninitializedAccount),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+
+    /// Serialize the settings into companion account data and zero any
+    /// leftover bytes.
+    pub fn overwrite_data(&self, data: &mut [u8]) -> Result<(), ProgramError> {
+        data.fill(0);
+        bincode::serialize_into(data, &ProgramState::LookupTableSettings(self.clone()))
+            .map_err(|_| ProgramError::InvalidAccountData)
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, AbiExample)]
 pub struct AddressLookupTable<'a> {
     pub meta: LookupTableMeta,
@@ -197,6 +796,40 @@ impl<'a> AddressLookupTable<'a> {
         Ok(active_addresses_len)
     }
 
+    /// Get the lookup status of the address stored at a table index. Fails
+    /// for indexes without a stored address, and for tables which are no
+    /// longer active, in the same way as `lookup`.
+    pub fn index_status(
+        &self,
+        current_slot: Slot,
+        index: u8,
+    ) -> Result<LookupIndexStatus, AddressLookupError> {
+        let active_addresses_len = self.get_active_addresses_len(current_slot)?;
+        let index = index as usize;
+        if index < active_addresses_len {
+            Ok(LookupIndexStatus::Active)
+        } else if index < self.addresses.len() {
+            Ok(LookupIndexStatus::Pending {
+                active_slot: self.meta.last_extended_slot.saturating_add(1),
+            })
+        } else {
+            Err(AddressLookupError::InvalidLookupIndex)
+        }
+    }
+
+    /// Get the lookup status of the addresses stored at each provided table
+    /// index, such as a range of indexes
+    pub fn index_statuses(
+        &self,
+        current_slot: Slot,
+        indexes: impl IntoIterator<Item = u8>,
+    ) -> Result<Vec<LookupIndexStatus>, AddressLookupError> {
+        indexes
+            .into_iter()
+            .map(|index| self.index_status(current_slot, index))
+            .collect()
+    }
+
     /// Lookup addresses for provided table indexes. Since lookups are performed
     /// on tables which are not read-locked, this implementation needs to be
     /// careful about resolving addresses consistently.
@@ -214,6 +847,30 @@ impl<'a> AddressLookupTable<'a> {
             .ok_or(AddressLookupError::InvalidLookupIndex)
     }
 
+    /// Lookup the writable and readonly addresses of a v0 message's table
+    /// lookup, failing with the same errors as the runtime when it loads the
+    /// lookup's addresses for a transaction. Duplicate indexes are loaded
+    /// like any other index, as in the runtime. Transactions which load an
+    /// account twice are rejected afterwards, when the loaded message is
+    /// sanitized, with `TransactionError::AccountLoadedTwice`.
+    #[cfg(not(target_os = "solana"))]
+    pub fn lookup_message_addresses(
+        &self,
+        current_slot: Slot,
+        table_lookup: &MessageAddressTableLookup,
+    ) -> Result<LoadedAddresses, AddressLoaderError> {
+        Ok(LoadedAddresses {
+            writable: self.lookup(current_slot, &table_lookup.writable_indexes)?,
+            readonly: self.lookup(current_slot, &table_lookup.readonly_indexes)?,
+        })
+    }
+
+    /// Hash a list of table addresses, committing to both their order and
+    /// their count.
+    pub fn hash_addresses(addresses: &[Pubkey]) -> Hash {
+        hashv(&[bytemuck::cast_slice(addresses)])
+    }
+
     /// Serialize an address table including its addresses
     pub fn serialize_for_tests(self) -> Result<Vec<u8>, ProgramError> {
         let mut data = vec![0; LOOKUP_TABLE_META_SIZE];
@@ -248,6 +905,10 @@ impl<'a> AddressLookupTable<'a> {
         let meta = match program_state {
             ProgramState::LookupTable(meta) => Ok(meta),
             ProgramState::Uninitialized => Err(ProgramError::UninitializedAccount),
+            ProgramState::LookupTableFunders(_)
+            | ProgramState::LookupTableLabels(_)
+            | ProgramState::LookupTableRegistry(_)
+            | ProgramState::LookupTableSettings(_) => Err(ProgramError::InvalidAccountData),
         }?;
 
         let raw_addresses_data = data.get(LOOKUP_TABLE_META_SIZE..).ok_or({
@@ -272,7 +933,7 @@ impl<'a> AddressLookupTable<'a> {
 mod tests {
     use {
         super::*,
-        solana_sdk::{hash::Hash, slot_hashes::SlotHashes},
+        solana_sdk::{hash::Hash, pubkey::PUBKEY_BYTES, slot_hashes::SlotHashes},
     };
 
     impl AddressLookupTable<'_> {
@@ -308,6 +969,42 @@ mod tests {
         assert_eq!(meta_size as usize, 24);
     }
 
+    #[test]
+    fn test_lookup_table_data_len() {
+        assert_eq!(lookup_table_data_len(0), Ok(LOOKUP_TABLE_META_SIZE));
+        assert_eq!(
+            lookup_table_data_len(10),
+            Ok(LOOKUP_TABLE_META_SIZE + 10 * PUBKEY_BYTES)
+        );
+        assert_eq!(
+            lookup_table_data_len(usize::MAX),
+            Err(ProgramError::ArithmeticOverflow)
+        );
+    }
+
+    #[test]
+    fn test_lookup_table_rent() {
+        let rent = Rent::default();
+        assert_eq!(
+            lookup_table_rent_exempt_balance(&rent, 10),
+            Ok(rent.minimum_balance(LOOKUP_TABLE_META_SIZE + 10 * PUBKEY_BYTES))
+        );
+        assert_eq!(
+            lookup_table_extension_lamports(&rent, 10, 12),
+            Ok(
+                rent.minimum_balance(LOOKUP_TABLE_META_SIZE + 12 * PUBKEY_BYTES)
+                    - rent.minimum_balance(LOOKUP_TABLE_META_SIZE + 10 * PUBKEY_BYTES)
+            )
+        );
+        assert_eq!(lookup_table_extension_lamports(&rent, 12, 10), Ok(0));
+
+        let free_rent = Rent {
+            lamports_per_byte_year: 0,
+            ..Rent::default()
+        };
+        assert_eq!(rent_exempt_balance(&free_rent, 0), 1);
+    }
+
     #[test]
     fn test_lookup_table_meta_status() {
         let mut slot_hashes = SlotHashes::default();
@@ -378,6 +1075,87 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_lookup_table_meta_closable_at_slot() {
+        assert_eq!(LookupTableMeta::default().closable_at_slot(), None);
+        assert_eq!(LookupTableMeta::default().slots_until_closable(0), None);
+
+        let deactivation_slot = 1_000;
+        let meta = LookupTableMeta {
+            deactivation_slot,
+            ..LookupTableMeta::default()
+        };
+        let closable_slot = deactivation_slot + MAX_ENTRIES as Slot;
+        assert_eq!(meta.closable_at_slot(), Some(closable_slot));
+
+        assert_eq!(
+            meta.status(deactivation_slot),
+            LookupTableStatus::Deactivating {
+                remaining_blocks: MAX_ENTRIES + 1,
+            }
+        );
+        assert_eq!(
+            meta.slots_until_closable(deactivation_slot),
+            Some(MAX_ENTRIES as Slot)
+        );
+
+        assert_eq!(
+            meta.status(closable_slot - 1),
+            LookupTableStatus::Deactivating {
+                remaining_blocks: 1,
+            }
+        );
+        assert_eq!(meta.slots_until_closable(closable_slot - 1), Some(1));
+
+        assert_eq!(meta.status(closable_slot), LookupTableStatus::Deactivated);
+        assert_eq!(meta.slots_until_closable(closable_slot), Some(0));
+        assert_eq!(meta.slots_until_closable(closable_slot + 1), Some(0));
+    }
+
+    #[test]
+    fn test_lookup_table_meta_authority_state() {
+        let authority = Pubkey::new_unique();
+        let current_slot = 100;
+
+        let settings = LookupTableSettings::new(Pubkey::new_unique());
+
+        let mutable_table = LookupTableMeta::new(authority);
+        assert_eq!(
+            mutable_table.authority_state(&settings, current_slot),
+            LookupTableAuthorityState::Mutable { authority }
+        );
+        assert!(!mutable_table.is_frozen(&settings, current_slot));
+
+        let frozen_table = LookupTableMeta::default();
+        assert_eq!(
+            frozen_table.authority_state(&settings, current_slot),
+            LookupTableAuthorityState::Frozen
+        );
+        assert!(frozen_table.is_frozen(&settings, current_slot));
+
+        let time_locked_settings = LookupTableSettings {
+            frozen_until: Some(current_slot + 1),
+            ..settings
+        };
+        assert_eq!(
+            mutable_table.authority_state(&time_locked_settings, current_slot),
+            LookupTableAuthorityState::FrozenUntil {
+                authority,
+                slot: current_slot + 1,
+            }
+        );
+        assert!(mutable_table.is_frozen(&time_locked_settings, current_slot));
+        assert_eq!(
+            mutable_table.authority_state(&time_locked_settings, current_slot + 1),
+            LookupTableAuthorityState::Mutable { authority }
+        );
+        assert!(!mutable_table.is_frozen(&time_locked_settings, current_slot + 1));
+        assert_eq!(
+            frozen_table.authority_state(&time_locked_settings, current_slot),
+            LookupTableAuthorityState::Frozen
+        );
+    }
+
     #[test]
     fn test_overwrite_meta_data() {
         let meta = LookupTableMeta::new_for_tests();
@@ -421,6 +1199,70 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_deserialize_append_only() {
+        let mut lookup_table_meta = LookupTableMeta::new_for_tests();
+        lookup_table_meta.set_append_only();
+        let address_table = AddressLookupTable::new_for_tests(lookup_table_meta, 10);
+        let address_table_data =
+            AddressLookupTable::serialize_for_tests(address_table.clone()).unwrap();
+        assert_eq!(
+            address_table_data.len(),
+            LOOKUP_TABLE_META_SIZE + 10 * PUBKEY_BYTES
+        );
+
+        let deserialized_table = AddressLookupTable::deserialize(&address_table_data).unwrap();
+        assert!(deserialized_table.meta.is_append_only());
+        assert_eq!(deserialized_table, address_table);
+
+        // The runtime ignores the flag and still reads a regular table
+        let runtime_table =
+            solana_program::address_lookup_table::state::AddressLookupTable::deserialize(
+                &address_table_data,
+            )
+            .unwrap();
+        assert_eq!(runtime_table.addresses, address_table.addresses);
+        assert_eq!(runtime_table.meta.authority, address_table.meta.authority);
+    }
+
+    #[test]
+    fn test_lookup_table_meta_extend_mode() {
+        let mut meta = LookupTableMeta::new_for_tests();
+        assert_eq!(meta.extend_mode(), LookupTableExtendMode::Authority);
+
+        meta.set_append_only();
+        for extend_mode in [
+            LookupTableExtendMode::Open,
+            LookupTableExtendMode::OpenDeduplicated,
+            LookupTableExtendMode::Authority,
+        ] {
+            meta.set_extend_mode(extend_mode);
+            assert_eq!(meta.extend_mode(), extend_mode);
+            assert!(meta.is_append_only());
+        }
+    }
+
+    #[test]
+    fn test_hash_addresses() {
+        let addresses: Vec<_> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let hash = AddressLookupTable::hash_addresses(&addresses);
+        assert_eq!(
+            hash,
+            hashv(&[
+                addresses[0].as_ref(),
+                addresses[1].as_ref(),
+                addresses[2].as_ref()
+            ]),
+        );
+
+        let reordered_addresses = vec![addresses[1], addresses[0], addresses[2]];
+        assert_ne!(
+            AddressLookupTable::hash_addresses(&reordered_addresses),
+            hash
+        );
+        assert_ne!(AddressLookupTable::hash_addresses(&addresses[..2]), hash);
+    }
+
     #[test]
     fn test_lookup_from_empty_table() {
         let lookup_table = AddressLookupTable {
@@ -506,6 +1348,115 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_lookup_index_status() {
+        let current_slot = 10;
+        let mut lookup_table = AddressLookupTable::new_for_tests(
+            LookupTableMeta {
+                last_extended_slot: current_slot,
+                last_extended_slot_start_index: 2,
+                ..LookupTableMeta::default()
+            },
+            3,
+        );
+
+        assert_eq!(
+            lookup_table.index_statuses(current_slot, 0..3),
+            Ok(vec![
+                LookupIndexStatus::Active,
+                LookupIndexStatus::Active,
+                LookupIndexStatus::Pending {
+                    active_slot: current_slot + 1,
+                },
+            ])
+        );
+        assert_eq!(
+            lookup_table.index_status(current_slot, 3),
+            Err(AddressLookupError::InvalidLookupIndex)
+        );
+        assert_eq!(
+            lookup_table.index_statuses(current_slot, [0, 3]),
+            Err(AddressLookupError::InvalidLookupIndex)
+        );
+
+        // Pending addresses are active from the reported slot
+        assert_eq!(
+            lookup_table.index_status(current_slot + 1, 2),
+            Ok(LookupIndexStatus::Active)
+        );
+        assert_eq!(
+            lookup_table.lookup(current_slot + 1, &[2]),
+            Ok(vec![lookup_table.addresses[2]])
+        );
+
+        lookup_table.meta.deactivation_slot = 0;
+        assert_eq!(
+            lookup_table.index_status(current_slot + MAX_ENTRIES as Slot, 0),
+            Err(AddressLookupError::LookupTableAccountNotFound)
+        );
+    }
+
+    #[test]
+    fn test_lookup_message_addresses() {
+        let current_slot = 10;
+        let lookup_table = AddressLookupTable::new_for_tests(
+            LookupTableMeta {
+                last_extended_slot: current_slot,
+                last_extended_slot_start_index: 4,
+                ..LookupTableMeta::default()
+            },
+            5,
+        );
+        let table_lookup =
+            |writable_indexes: Vec<u8>, readonly_indexes: Vec<u8>| MessageAddressTableLookup {
+                account_key: Pubkey::new_unique(),
+                writable_indexes,
+                readonly_indexes,
+            };
+
+        assert_eq!(
+            lookup_table.lookup_message_addresses(current_slot, &table_lookup(vec![2, 0], vec![3])),
+            Ok(LoadedAddresses {
+                writable: vec![lookup_table.addresses[2], lookup_table.addresses[0]],
+                readonly: vec![lookup_table.addresses[3]],
+            })
+        );
+
+        // Out of range and recently extended indexes
+        for (writable_indexes, readonly_indexes) in [(vec![5], vec![]), (vec![], vec![4])] {
+            assert_eq!(
+                lookup_table.lookup_message_addresses(
+                    current_slot,
+                    &table_lookup(writable_indexes, readonly_indexes)
+                ),
+                Err(AddressLoaderError::InvalidLookupIndex)
+            );
+        }
+
+        // Duplicate indexes are left for transaction sanitization to reject
+        assert_eq!(
+            lookup_table.lookup_message_addresses(current_slot, &table_lookup(vec![0, 1], vec![1])),
+            Ok(LoadedAddresses {
+                writable: vec![lookup_table.addresses[0], lookup_table.addresses[1]],
+                readonly: vec![lookup_table.addresses[1]],
+            })
+        );
+
+        // Inactive tables take precedence over invalid indexes
+        let deactivated_table = AddressLookupTable {
+            meta: LookupTableMeta {
+                deactivation_slot: 0,
+                ..lookup_table.meta.clone()
+            },
+            addresses: lookup_table.addresses.clone(),
+        };
+        assert_eq!(
+            deactivated_table
+                .lookup_message_addresses(MAX_ENTRIES as Slot, &table_lookup(vec![1, 1], vec![])),
+            Err(AddressLoaderError::LookupTableAccountNotFound)
+        );
+    }
+
     #[test]
     fn test_lookup_from_table_extended_in_previous_slot() {
         let current_slot = 1;
@@ -528,4 +1479,196 @@ mod tests {
             Err(AddressLookupError::InvalidLookupIndex),
         );
     }
+
+    #[test]
+    fn test_lookup_table_funders_record() {
+        let funder_1 = Pubkey::new_unique();
+        let funder_2 = Pubkey::new_unique();
+        let mut funders = LookupTableFunders::new(Pubkey::new_unique());
+        funders.record(funder_1, 100).unwrap();
+        funders.record(funder_2, 50).unwrap();
+        funders.record(funder_1, 25).unwrap();
+
+        assert_eq!(
+            funders.funders,
+            vec![
+                LookupTableFunder {
+                    address: funder_1,
+                    lamports: 125,
+                },
+                LookupTableFunder {
+                    address: funder_2,
+                    lamports: 50,
+                },
+            ]
+        );
+        assert_eq!(
+            funders.record(funder_2, u64::MAX),
+            Err(ProgramError::ArithmeticOverflow)
+        );
+
+        let mut data = vec![0; funders.serialized_size().unwrap()];
+        funders.overwrite_data(&mut data).unwrap();
+        assert_eq!(LookupTableFunders::deserialize(&data).unwrap(), funders);
+        assert_eq!(
+            LookupTableFunders::deserialize(&[0u8; LOOKUP_TABLE_META_SIZE]),
+            Err(ProgramError::UninitializedAccount)
+        );
+        assert_eq!(
+            AddressLookupTable::deserialize(&data).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn test_lookup_table_funders_record_max_funders() {
+        let mut funders = LookupTableFunders::new(Pubkey::new_unique());
+        for _ in 0..LOOKUP_TABLE_MAX_FUNDERS {
+            funders.record(Pubkey::new_unique(), 1).unwrap();
+        }
+
+        // Recorded funders can still contribute, but new funders can't
+        let recorded_funder = funders.funders[0].address;
+        funders.record(recorded_funder, 1).unwrap();
+        assert_eq!(funders.funders[0].lamports, 2);
+        assert_eq!(
+            funders.record(Pubkey::new_unique(), 1),
+            Err(AddressLookupError::TooManyFunders.into())
+        );
+        assert_eq!(funders.funders.len(), LOOKUP_TABLE_MAX_FUNDERS);
+    }
+
+    #[test]
+    fn test_lookup_table_funders_refunds() {
+        let mut funders = LookupTableFunders::new(Pubkey::new_unique());
+        funders.record(Pubkey::new_unique(), 300).unwrap();
+        funders.record(Pubkey::new_unique(), 100).unwrap();
+
+        let refunded_lamports = |available_lamports| {
+            let (refunds, remainder) = funders.refunds(available_lamports).unwrap();
+            let lamports: Vec<u64> = refunds.iter().map(|refund| refund.lamports).collect();
+            (lamports, remainder)
+        };
+
+        // Contributions are covered, with the rest left as the remainder
+        assert_eq!(refunded_lamports(1_000), (vec![300, 100], 600));
+        assert_eq!(refunded_lamports(400), (vec![300, 100], 0));
+        // Contributions are refunded pro rata, with rounding left as the
+        // remainder
+        assert_eq!(refunded_lamports(200), (vec![150, 50], 0));
+        assert_eq!(refunded_lamports(99), (vec![74, 24], 1));
+        assert_eq!(refunded_lamports(0), (vec![0, 0], 0));
+
+        assert_eq!(LookupTableFunders::default().refunds(10), Ok((vec![], 10)));
+    }
+
+    #[test]
+    fn test_lookup_table_labels() {
+        let mut labels = LookupTableLabels::new(Pubkey::new_unique(), Pubkey::new_unique());
+        labels.set_label(2, "mint".to_string());
+        labels.set_label(0, "program".to_string());
+        labels.set_label(2, "usdc mint".to_string());
+
+        assert_eq!(
+            labels.labels,
+            vec![
+                LookupTableLabel {
+                    index: 0,
+                    label: "program".to_string(),
+                },
+                LookupTableLabel {
+                    index: 2,
+                    label: "usdc mint".to_string(),
+                },
+            ]
+        );
+        assert_eq!(labels.label(2), Some("usdc mint"));
+        assert_eq!(labels.label(1), None);
+
+        assert!(labels.clear_label(0));
+        assert!(!labels.clear_label(0));
+        assert_eq!(labels.label(0), None);
+
+        let mut data = vec![0; labels.serialized_size().unwrap()];
+        labels.overwrite_data(&mut data).unwrap();
+        assert_eq!(LookupTableLabels::deserialize(&data).unwrap(), labels);
+        assert_eq!(
+            AddressLookupTable::deserialize(&data).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+        assert_eq!(
+            LookupTableFunders::deserialize(&data),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn test_lookup_table_labels_join() {
+        let address_table = AddressLookupTable::new_for_tests(LookupTableMeta::new_for_tests(), 3);
+        let mut labels = LookupTableLabels::new(Pubkey::new_unique(), Pubkey::new_unique());
+        labels.set_label(1, "mint".to_string());
+        // Labels past the end of the table are ignored
+        labels.set_label(5, "stale".to_string());
+
+        assert_eq!(
+            labels.join(&address_table.addresses),
+            vec![
+                (address_table.addresses[0], None),
+                (address_table.addresses[1], Some("mint")),
+                (address_table.addresses[2], None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lookup_table_registry() {
+        let [table_a, table_b] = [(); 2].map(|_| Pubkey::new_unique());
+        let mut registry = LookupTableRegistry::new(Pubkey::new_unique(), u8::MAX);
+        assert!(registry.register(table_a));
+        assert!(registry.register(table_b));
+        assert!(!registry.register(table_a));
+        assert_eq!(registry.lookup_tables, vec![table_a, table_b]);
+
+        assert!(registry.unregister(&table_a));
+        assert!(!registry.unregister(&table_a));
+        assert_eq!(registry.lookup_tables, vec![table_b]);
+
+        let mut data = vec![0; registry.serialized_size().unwrap()];
+        registry.overwrite_data(&mut data).unwrap();
+        assert_eq!(LookupTableRegistry::deserialize(&data).unwrap(), registry);
+        assert_eq!(
+            AddressLookupTable::deserialize(&data).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+        assert_eq!(
+            LookupTableRegistry::deserialize(&[0; 4]),
+            Err(ProgramError::UninitializedAccount)
+        );
+    }
+
+    #[test]
+    fn test_lookup_table_settings() {
+        let settings = LookupTableSettings {
+            extend_hook: Some(Pubkey::new_unique()),
+            frozen_until: Some(Slot::MAX),
+            successor: Some(Pubkey::new_unique()),
+            scheduled_deactivation_slot: Some(Slot::MAX),
+            ..LookupTableSettings::new(Pubkey::new_unique())
+        };
+        let settings_size =
+            bincode::serialized_size(&ProgramState::LookupTableSettings(settings.clone())).unwrap();
+        assert!(settings_size as usize <= LOOKUP_TABLE_SETTINGS_SIZE);
+
+        let mut data = vec![0; LOOKUP_TABLE_SETTINGS_SIZE];
+        settings.overwrite_data(&mut data).unwrap();
+        assert_eq!(LookupTableSettings::deserialize(&data).unwrap(), settings);
+        assert_eq!(
+            AddressLookupTable::deserialize(&data).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+        assert_eq!(
+            LookupTableSettings::deserialize(&[0; LOOKUP_TABLE_SETTINGS_SIZE]),
+            Err(ProgramError::UninitializedAccount)
+        );
+    }
 }
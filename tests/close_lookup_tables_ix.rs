@@ -0,0 +1,281 @@
+#![cfg(feature = "test-sbf")]
+
+use {
+    assert_matches::assert_matches,
+    common::{
+        add_deactivated_lookup_table_with_funders, add_lookup_table_account,
+        add_registered_lookup_table, assert_ix_error, get_lookup_table_registry,
+        lookup_tables_instruction_error, new_address_lookup_table, process_ix_with_signers,
+        setup_test_context,
+    },
+    solana_program_test::*,
+    solana_programs_address_lookup_table::{
+        instruction::{
+            close_lookup_table_with_funders, close_lookup_tables, close_registered_lookup_table,
+        },
+        state::LookupTableFunder,
+    },
+    solana_sdk::{
+        account::ReadableAccount,
+        instruction::InstructionError,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        slot_hashes::SlotHashes,
+        sysvar::Sysvar,
+        transaction::Transaction,
+    },
+};
+
+mod common;
+
+#[tokio::test]
+async fn test_close_lookup_tables() {
+    let mut context = setup_test_context().await;
+    context
+        .warp_to_slot(SlotHashes::size_of() as u64 + 1)
+        .unwrap();
+
+    let authority_keypair = Keypair::new();
+    let mut lookup_table_addresses = vec![];
+    let mut total_table_lamports = 0;
+    for num_addresses in [0, 10, 256] {
+        let initialized_table = {
+            let mut table =
+                new_address_lookup_table(Some(authority_keypair.pubkey()), num_addresses);
+            table.meta.deactivation_slot = 0;
+            table
+        };
+        let lookup_table_address = Pubkey::new_unique();
+        let table_account =
+            add_lookup_table_account(&mut context, lookup_table_address, initialized_table).await;
+        total_table_lamports += table_account.lamports();
+        lookup_table_addresses.push(lookup_table_address);
+    }
+
+    let recipient_address = Pubkey::new_unique();
+    let client = &mut context.banks_client;
+    let payer = &context.payer;
+    let recent_blockhash = context.last_blockhash;
+    let transaction = Transaction::new_signed_with_payer(
+        &[close_lookup_tables(
+            authority_keypair.pubkey(),
+            recipient_address,
+            &lookup_table_addresses,
+        )],
+        Some(&payer.pubkey()),
+        &[payer, &authority_keypair],
+        recent_blockhash,
+    );
+
+    assert_matches!(client.process_transaction(transaction).await, Ok(()));
+    for lookup_table_address in lookup_table_addresses {
+        assert!(client
+            .get_account(lookup_table_address)
+            .await
+            .unwrap()
+            .is_none());
+    }
+    assert_eq!(
+        client.get_balance(recipient_address).await.unwrap(),
+        total_table_lamports
+    );
+}
+
+#[tokio::test]
+async fn test_close_lookup_tables_with_one_not_deactivated() {
+    let mut context = setup_test_context().await;
+    context
+        .warp_to_slot(SlotHashes::size_of() as u64 + 1)
+        .unwrap();
+
+    let authority_keypair = Keypair::new();
+    let deactivated_table = {
+        let mut table = new_address_lookup_table(Some(authority_keypair.pubkey()), 0);
+        table.meta.deactivation_slot = 0;
+        table
+    };
+    let deactivated_lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(
+        &mut context,
+        deactivated_lookup_table_address,
+        deactivated_table,
+    )
+    .await;
+
+    let initialized_table = new_address_lookup_table(Some(authority_keypair.pubkey()), 0);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(&mut context, lookup_table_address, initialized_table).await;
+
+    let ix = close_lookup_tables(
+        authority_keypair.pubkey(),
+        context.payer.pubkey(),
+        &[deactivated_lookup_table_address, lookup_table_address],
+    );
+
+    // The ix should fail because the second table hasn't been deactivated yet
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority_keypair),
+        lookup_tables_instruction_error(1, ProgramError::InvalidArgument),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_close_lookup_tables_with_table_as_recipient() {
+    let mut context = setup_test_context().await;
+    context
+        .warp_to_slot(SlotHashes::size_of() as u64 + 1)
+        .unwrap();
+
+    let authority_keypair = Keypair::new();
+    let initialized_table = {
+        let mut table = new_address_lookup_table(Some(authority_keypair.pubkey()), 0);
+        table.meta.deactivation_slot = 0;
+        table
+    };
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(&mut context, lookup_table_address, initialized_table).await;
+
+    let ix = close_lookup_tables(
+        authority_keypair.pubkey(),
+        lookup_table_address,
+        &[lookup_table_address],
+    );
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority_keypair),
+        lookup_tables_instruction_error(0, ProgramError::InvalidArgument),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_close_no_lookup_tables() {
+    let mut context = setup_test_context().await;
+
+    let authority_keypair = Keypair::new();
+    let ix = close_lookup_tables(authority_keypair.pubkey(), Pubkey::new_unique(), &[]);
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority_keypair),
+        InstructionError::NotEnoughAccountKeys,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_close_lookup_tables_with_companion_accounts() {
+    let mut context = setup_test_context().await;
+
+    let authority_keypair = Keypair::new();
+    let funder_address = Pubkey::new_unique();
+    let funded_lookup_table_address = add_deactivated_lookup_table_with_funders(
+        &mut context,
+        authority_keypair.pubkey(),
+        2_000_000_000,
+        1_000_000_000,
+        &[LookupTableFunder {
+            address: funder_address,
+            lamports: 1_000_000_000,
+        }],
+    )
+    .await;
+    let registered_lookup_table_address =
+        add_registered_lookup_table(&mut context, authority_keypair.pubkey(), Some(0)).await;
+
+    // The funders and registry accounts follow the tables
+    let recipient_address = Pubkey::new_unique();
+    let mut ix = close_lookup_tables(
+        authority_keypair.pubkey(),
+        recipient_address,
+        &[funded_lookup_table_address, registered_lookup_table_address],
+    );
+    ix.accounts.extend_from_slice(
+        &close_lookup_table_with_funders(
+            funded_lookup_table_address,
+            authority_keypair.pubkey(),
+            recipient_address,
+            &[funder_address],
+        )
+        .accounts[3..],
+    );
+    ix.accounts.extend_from_slice(
+        &close_registered_lookup_table(
+            registered_lookup_table_address,
+            authority_keypair.pubkey(),
+            recipient_address,
+        )
+        .accounts[3..],
+    );
+    process_ix_with_signers(&mut context, ix, &[&authority_keypair]).await;
+
+    for lookup_table_address in [funded_lookup_table_address, registered_lookup_table_address] {
+        assert!(context
+            .banks_client
+            .get_account(lookup_table_address)
+            .await
+            .unwrap()
+            .is_none());
+    }
+    assert_eq!(
+        context
+            .banks_client
+            .get_balance(funder_address)
+            .await
+            .unwrap(),
+        1_000_000_000
+    );
+    let registry = get_lookup_table_registry(&mut context, authority_keypair.pubkey()).await;
+    assert!(!registry
+        .lookup_tables
+        .contains(&registered_lookup_table_address));
+}
+
+#[tokio::test]
+async fn test_close_lookup_tables_without_companion_accounts() {
+    let mut context = setup_test_context().await;
+    context
+        .warp_to_slot(SlotHashes::size_of() as u64 + 1)
+        .unwrap();
+
+    let authority_keypair = Keypair::new();
+    let deactivated_table = {
+        let mut table = new_address_lookup_table(Some(authority_keypair.pubkey()), 0);
+        table.meta.deactivation_slot = 0;
+        table
+    };
+    let deactivated_lookup_table_address = Pubkey::new_unique();
+    let registered_lookup_table_address =
+        add_registered_lookup_table(&mut context, authority_keypair.pubkey(), Some(0)).await;
+    add_lookup_table_account(
+        &mut context,
+        deactivated_lookup_table_address,
+        deactivated_table,
+    )
+    .await;
+
+    // The registered table at index 1 can't be closed without its registry
+    let ix = close_lookup_tables(
+        authority_keypair.pubkey(),
+        context.payer.pubkey(),
+        &[
+            deactivated_lookup_table_address,
+            registered_lookup_table_address,
+        ],
+    );
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority_keypair),
+        lookup_tables_instruction_error(1, ProgramError::NotEnoughAccountKeys),
+    )
+    .await;
+}
@@ -2,13 +2,28 @@
 #![cfg(feature = "test-sbf")]
 
 use {
+    assert_matches::assert_matches,
     solana_program_test::*,
-    solana_programs_address_lookup_table::state::{AddressLookupTable, LookupTableMeta},
+    solana_programs_address_lookup_table::{
+        error::lookup_tables_error,
+        instruction::{
+            derive_lookup_table_funders_address, derive_lookup_table_labels_address,
+            derive_lookup_table_registry_address, derive_lookup_table_settings_address,
+        },
+        state::{
+            AddressLookupTable, LookupTableExtendMode, LookupTableFunder, LookupTableFunders,
+            LookupTableLabels, LookupTableMeta, LookupTableRegistry, LookupTableSettings,
+            LOOKUP_TABLE_SETTINGS_SIZE,
+        },
+    },
     solana_sdk::{
-        account::AccountSharedData,
+        account::{Account, AccountSharedData, WritableAccount},
         instruction::{Instruction, InstructionError},
+        program_error::ProgramError,
         pubkey::Pubkey,
         signature::{Keypair, Signer},
+        slot_hashes::SlotHashes,
+        sysvar::Sysvar,
         transaction::{Transaction, TransactionError},
     },
     std::borrow::Cow,
@@ -57,6 +72,59 @@ pub async fn assert_ix_error(
     );
 }
 
+/// Processes an instruction signed by the payer and the given signers,
+/// asserting that it succeeds
+/// Returns the instruction error of a bulk lookup tables instruction when
+/// the table at `index` fails with `error`
+pub fn lookup_tables_instruction_error(index: u8, error: ProgramError) -> InstructionError {
+    let ProgramError::Custom(error_code) = lookup_tables_error(index, error) else {
+        unreachable!("Bulk lookup tables errors are custom errors");
+    };
+    InstructionError::Custom(error_code)
+}
+
+pub async fn process_ix_with_signers(
+    context: &mut ProgramTestContext,
+    ix: Instruction,
+    signers: &[&Keypair],
+) {
+    let client = &mut context.banks_client;
+    let payer = &context.payer;
+    let recent_blockhash = client
+        .get_new_latest_blockhash(&context.last_blockhash)
+        .await
+        .unwrap();
+    context.last_blockhash = recent_blockhash;
+
+    let mut all_signers = vec![payer];
+    all_signers.extend_from_slice(signers);
+    let transaction = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer.pubkey()),
+        &all_signers,
+        recent_blockhash,
+    );
+
+    assert_matches!(client.process_transaction(transaction).await, Ok(()));
+}
+
+/// Processes an instruction signed by the payer and the table authority,
+/// returning the lookup table account afterwards
+pub async fn process_ix(
+    context: &mut ProgramTestContext,
+    ix: Instruction,
+    authority: &Keypair,
+    lookup_table_address: Pubkey,
+) -> Account {
+    process_ix_with_signers(context, ix, &[authority]).await;
+    context
+        .banks_client
+        .get_account(lookup_table_address)
+        .await
+        .unwrap()
+        .unwrap()
+}
+
 pub fn new_address_lookup_table(
     authority: Option<Pubkey>,
     num_addresses: usize,
@@ -91,3 +159,170 @@ pub async fn add_lookup_table_account(
 
     account
 }
+
+pub fn new_append_only_lookup_table(
+    authority: Option<Pubkey>,
+    num_addresses: usize,
+) -> AddressLookupTable<'static> {
+    let mut table = new_address_lookup_table(authority, num_addresses);
+    table.meta.set_append_only();
+    table
+}
+
+pub fn new_open_lookup_table(
+    authority: Option<Pubkey>,
+    num_addresses: usize,
+    extend_mode: LookupTableExtendMode,
+) -> AddressLookupTable<'static> {
+    let mut table = new_address_lookup_table(authority, num_addresses);
+    table.meta.set_extend_mode(extend_mode);
+    table
+}
+
+/// Adds a lookup table account along with its settings account
+pub async fn add_lookup_table_account_with_settings(
+    context: &mut ProgramTestContext,
+    account_address: Pubkey,
+    mut address_lookup_table: AddressLookupTable<'static>,
+    settings: LookupTableSettings,
+) -> AccountSharedData {
+    address_lookup_table.meta.set_has_settings();
+    let table_account =
+        add_lookup_table_account(context, account_address, address_lookup_table).await;
+
+    let mut data = vec![0; LOOKUP_TABLE_SETTINGS_SIZE];
+    settings.overwrite_data(&mut data).unwrap();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let mut settings_account = AccountSharedData::new(
+        rent.minimum_balance(data.len()),
+        data.len(),
+        &solana_programs_address_lookup_table::id(),
+    );
+    settings_account.set_data_from_slice(&data);
+    let (settings_address, _) = derive_lookup_table_settings_address(&account_address);
+    context.set_account(&settings_address, &settings_account);
+
+    table_account
+}
+
+pub async fn get_lookup_table_funders(
+    context: &mut ProgramTestContext,
+    lookup_table_address: Pubkey,
+) -> LookupTableFunders {
+    let (funders_address, _) = derive_lookup_table_funders_address(&lookup_table_address);
+    let funders_account = get_rent_exempt_account(context, funders_address).await;
+    LookupTableFunders::deserialize(&funders_account.data).unwrap()
+}
+
+pub async fn get_lookup_table_labels(
+    context: &mut ProgramTestContext,
+    lookup_table_address: Pubkey,
+) -> LookupTableLabels {
+    let (labels_address, _) = derive_lookup_table_labels_address(&lookup_table_address);
+    let labels_account = get_rent_exempt_account(context, labels_address).await;
+    LookupTableLabels::deserialize(&labels_account.data).unwrap()
+}
+
+pub async fn get_lookup_table_settings(
+    context: &mut ProgramTestContext,
+    lookup_table_address: Pubkey,
+) -> LookupTableSettings {
+    let (settings_address, _) = derive_lookup_table_settings_address(&lookup_table_address);
+    let settings_account = get_rent_exempt_account(context, settings_address).await;
+    LookupTableSettings::deserialize(&settings_account.data).unwrap()
+}
+
+pub async fn get_lookup_table_registry(
+    context: &mut ProgramTestContext,
+    authority_address: Pubkey,
+) -> LookupTableRegistry {
+    let (registry_address, _) = derive_lookup_table_registry_address(&authority_address);
+    let registry_account = get_rent_exempt_account(context, registry_address).await;
+    LookupTableRegistry::deserialize(&registry_account.data).unwrap()
+}
+
+async fn get_rent_exempt_account(context: &mut ProgramTestContext, address: Pubkey) -> Account {
+    let account = context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .unwrap();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    assert!(rent.is_exempt(account.lamports, account.data.len()));
+    account
+}
+
+/// Adds a deactivated table which records its funders, along with its
+/// funders account
+pub async fn add_deactivated_lookup_table_with_funders(
+    context: &mut ProgramTestContext,
+    authority: Pubkey,
+    table_lamports: u64,
+    funders_lamports: u64,
+    funders: &[LookupTableFunder],
+) -> Pubkey {
+    context
+        .warp_to_slot(SlotHashes::size_of() as u64 + 1)
+        .unwrap();
+
+    let lookup_table_address = Pubkey::new_unique();
+    let (funders_address, _) = derive_lookup_table_funders_address(&lookup_table_address);
+
+    let mut table = new_address_lookup_table(Some(authority), 0);
+    table.meta.deactivation_slot = 0;
+    table.meta.set_records_funders();
+    let mut table_account = add_lookup_table_account(context, lookup_table_address, table).await;
+    table_account.set_lamports(table_lamports);
+    context.set_account(&lookup_table_address, &table_account);
+
+    let lookup_table_funders = LookupTableFunders {
+        lookup_table: lookup_table_address,
+        funders: funders.to_vec(),
+    };
+    let mut data = vec![0; lookup_table_funders.serialized_size().unwrap()];
+    lookup_table_funders.overwrite_data(&mut data).unwrap();
+    let mut funders_account = AccountSharedData::new(
+        funders_lamports,
+        data.len(),
+        &solana_programs_address_lookup_table::id(),
+    );
+    funders_account.set_data_from_slice(&data);
+    context.set_account(&funders_address, &funders_account);
+
+    lookup_table_address
+}
+
+/// Adds a table with 10 addresses which is listed in its authority's
+/// registry, along with the registry account
+pub async fn add_registered_lookup_table(
+    context: &mut ProgramTestContext,
+    authority: Pubkey,
+    deactivation_slot: Option<u64>,
+) -> Pubkey {
+    let lookup_table_address = Pubkey::new_unique();
+    let (registry_address, bump_seed) = derive_lookup_table_registry_address(&authority);
+
+    let mut table = new_address_lookup_table(Some(authority), 10);
+    if let Some(deactivation_slot) = deactivation_slot {
+        table.meta.deactivation_slot = deactivation_slot;
+    }
+    table.meta.set_registered(true);
+    add_lookup_table_account(context, lookup_table_address, table).await;
+
+    let mut registry = LookupTableRegistry::new(authority, bump_seed);
+    registry.register(Pubkey::new_unique());
+    registry.register(lookup_table_address);
+    let mut data = vec![0; registry.serialized_size().unwrap()];
+    registry.overwrite_data(&mut data).unwrap();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let mut registry_account = AccountSharedData::new(
+        rent.minimum_balance(data.len()),
+        data.len(),
+        &solana_programs_address_lookup_table::id(),
+    );
+    registry_account.set_data_from_slice(&data);
+    context.set_account(&registry_address, &registry_account);
+
+    lookup_table_address
+}
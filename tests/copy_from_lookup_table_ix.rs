@@ -0,0 +1,191 @@
+#![cfg(feature = "test-sbf")]
+
+use {
+    assert_matches::assert_matches,
+    common::{
+        add_lookup_table_account, assert_ix_error, new_address_lookup_table, setup_test_context,
+    },
+    solana_program_test::*,
+    solana_programs_address_lookup_table::{
+        error::AddressLookupError,
+        instruction::copy_from_lookup_table,
+        state::{AddressLookupTable, LOOKUP_TABLE_META_SIZE},
+    },
+    solana_sdk::{
+        account::{AccountSharedData, WritableAccount},
+        clock::Clock,
+        instruction::InstructionError,
+        pubkey::{Pubkey, PUBKEY_BYTES},
+        signature::{Keypair, Signer},
+        system_program,
+        transaction::Transaction,
+    },
+};
+
+mod common;
+
+#[tokio::test]
+async fn test_copy_from_lookup_table() {
+    let mut context = setup_test_context().await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    let authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 10);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(
+        &mut context,
+        lookup_table_address,
+        initialized_table.clone(),
+    )
+    .await;
+
+    // Copy from a frozen table controlled by a different authority
+    let source_table = new_address_lookup_table(None, 20);
+    let source_lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(
+        &mut context,
+        source_lookup_table_address,
+        source_table.clone(),
+    )
+    .await;
+
+    let client = &mut context.banks_client;
+    let payer = &context.payer;
+    let recent_blockhash = context.last_blockhash;
+    let transaction = Transaction::new_signed_with_payer(
+        &[copy_from_lookup_table(
+            lookup_table_address,
+            authority.pubkey(),
+            source_lookup_table_address,
+            Some(payer.pubkey()),
+            5,
+            10,
+        )],
+        Some(&payer.pubkey()),
+        &[payer, &authority],
+        recent_blockhash,
+    );
+
+    assert_matches!(client.process_transaction(transaction).await, Ok(()));
+    let table_account = client
+        .get_account(lookup_table_address)
+        .await
+        .unwrap()
+        .unwrap();
+    let expected_data_len = LOOKUP_TABLE_META_SIZE + 20 * PUBKEY_BYTES;
+    assert_eq!(table_account.data.len(), expected_data_len);
+    assert_eq!(
+        table_account.lamports,
+        rent.minimum_balance(expected_data_len)
+    );
+
+    let clock = client.get_sysvar::<Clock>().await.unwrap();
+    let lookup_table = AddressLookupTable::deserialize(&table_account.data).unwrap();
+    let mut expected_addresses = initialized_table.addresses.to_vec();
+    expected_addresses.extend_from_slice(&source_table.addresses[5..15]);
+    assert_eq!(lookup_table.addresses.to_vec(), expected_addresses);
+    assert_eq!(lookup_table.meta.last_extended_slot, clock.slot);
+    assert_eq!(lookup_table.meta.last_extended_slot_start_index, 10);
+}
+
+#[tokio::test]
+async fn test_copy_from_lookup_table_out_of_range() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 0);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(&mut context, lookup_table_address, initialized_table).await;
+
+    let source_table = new_address_lookup_table(None, 10);
+    let source_lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(&mut context, source_lookup_table_address, source_table).await;
+
+    for (start_index, num_addresses) in [(0, 11), (10, 1), (255, 2)] {
+        let ix = copy_from_lookup_table(
+            lookup_table_address,
+            authority.pubkey(),
+            source_lookup_table_address,
+            Some(context.payer.pubkey()),
+            start_index,
+            num_addresses,
+        );
+
+        assert_ix_error(
+            &mut context,
+            ix,
+            Some(&authority),
+            InstructionError::Custom(AddressLookupError::InvalidLookupIndex as u32),
+        )
+        .await;
+    }
+}
+
+#[tokio::test]
+async fn test_copy_from_lookup_table_with_invalid_source_owner() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 0);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(&mut context, lookup_table_address, initialized_table).await;
+
+    let source_table = new_address_lookup_table(None, 10);
+    let source_lookup_table_address = Pubkey::new_unique();
+    let mut source_account =
+        add_lookup_table_account(&mut context, source_lookup_table_address, source_table).await;
+    source_account.set_owner(system_program::id());
+    context.set_account(&source_lookup_table_address, &source_account);
+
+    let ix = copy_from_lookup_table(
+        lookup_table_address,
+        authority.pubkey(),
+        source_lookup_table_address,
+        Some(context.payer.pubkey()),
+        0,
+        10,
+    );
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        InstructionError::InvalidAccountOwner,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_copy_from_lookup_table_with_uninitialized_source() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 0);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(&mut context, lookup_table_address, initialized_table).await;
+
+    let source_lookup_table_address = Pubkey::new_unique();
+    let source_account = AccountSharedData::new(
+        1_000_000_000,
+        LOOKUP_TABLE_META_SIZE,
+        &solana_programs_address_lookup_table::id(),
+    );
+    context.set_account(&source_lookup_table_address, &source_account);
+
+    let ix = copy_from_lookup_table(
+        lookup_table_address,
+        authority.pubkey(),
+        source_lookup_table_address,
+        Some(context.payer.pubkey()),
+        0,
+        1,
+    );
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        InstructionError::UninitializedAccount,
+    )
+    .await;
+}
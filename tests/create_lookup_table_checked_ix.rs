@@ -0,0 +1,172 @@
+#![cfg(feature = "test-sbf")]
+
+use {
+    assert_matches::assert_matches,
+    common::{
+        add_lookup_table_account, assert_ix_error, new_address_lookup_table, setup_test_context,
+    },
+    solana_program_test::*,
+    solana_programs_address_lookup_table::{
+        instruction::{
+            create_lookup_table_checked, CreateLookupTableMode, CreateLookupTableOutcome,
+        },
+        state::{AddressLookupTable, LOOKUP_TABLE_META_SIZE},
+    },
+    solana_sdk::{
+        hash::Hash,
+        instruction::{Instruction, InstructionError},
+        pubkey::Pubkey,
+        signature::Signer,
+        transaction::Transaction,
+    },
+};
+
+mod common;
+
+async fn process_create_ix(
+    context: &mut ProgramTestContext,
+    ix: Instruction,
+    recent_blockhash: Hash,
+) -> CreateLookupTableOutcome {
+    let client = &mut context.banks_client;
+    let payer = &context.payer;
+    let transaction = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer.pubkey()),
+        &[payer],
+        recent_blockhash,
+    );
+
+    let result = client
+        .process_transaction_with_metadata(transaction)
+        .await
+        .unwrap();
+    assert_matches!(result.result, Ok(()));
+    let return_data = result.metadata.unwrap().return_data.unwrap();
+    assert_eq!(
+        return_data.program_id,
+        solana_programs_address_lookup_table::id()
+    );
+    bincode::deserialize(&return_data.data).unwrap()
+}
+
+#[tokio::test]
+async fn test_create_lookup_table_strict() {
+    let mut context = setup_test_context().await;
+
+    let test_recent_slot = 123;
+    context.warp_to_slot(test_recent_slot).unwrap();
+
+    let client = &mut context.banks_client;
+    let payer = &context.payer;
+    let recent_blockhash = context.last_blockhash;
+    let authority_address = Pubkey::new_unique();
+    let (create_ix, lookup_table_address) = create_lookup_table_checked(
+        authority_address,
+        payer.pubkey(),
+        test_recent_slot,
+        CreateLookupTableMode::Strict,
+    );
+
+    // First create should succeed
+    let transaction = Transaction::new_signed_with_payer(
+        &[create_ix.clone()],
+        Some(&payer.pubkey()),
+        &[payer],
+        recent_blockhash,
+    );
+    assert_matches!(client.process_transaction(transaction).await, Ok(()));
+    let lookup_table_account = client
+        .get_account(lookup_table_address)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(lookup_table_account.data.len(), LOOKUP_TABLE_META_SIZE);
+    let lookup_table = AddressLookupTable::deserialize(&lookup_table_account.data).unwrap();
+    assert_eq!(lookup_table.meta.authority, Some(authority_address));
+
+    // Second create should fail
+    context.last_blockhash = client
+        .get_new_latest_blockhash(&recent_blockhash)
+        .await
+        .unwrap();
+    assert_ix_error(
+        &mut context,
+        create_ix,
+        None,
+        InstructionError::AccountAlreadyInitialized,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_create_lookup_table_idempotent_reports_outcome() {
+    let mut context = setup_test_context().await;
+
+    let test_recent_slot = 123;
+    context.warp_to_slot(test_recent_slot).unwrap();
+
+    let authority_address = Pubkey::new_unique();
+    let (create_ix, lookup_table_address) = create_lookup_table_checked(
+        authority_address,
+        context.payer.pubkey(),
+        test_recent_slot,
+        CreateLookupTableMode::Idempotent,
+    );
+
+    // First create should report that the table was created
+    let recent_blockhash = context.last_blockhash;
+    assert_eq!(
+        process_create_ix(&mut context, create_ix.clone(), recent_blockhash).await,
+        CreateLookupTableOutcome::Created,
+    );
+    let lookup_table_account = context
+        .banks_client
+        .get_account(lookup_table_address)
+        .await
+        .unwrap()
+        .unwrap();
+    let lookup_table = AddressLookupTable::deserialize(&lookup_table_account.data).unwrap();
+    assert_eq!(lookup_table.meta.authority, Some(authority_address));
+
+    // Second create should report that the table already existed
+    let recent_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&recent_blockhash)
+        .await
+        .unwrap();
+    assert_eq!(
+        process_create_ix(&mut context, create_ix, recent_blockhash).await,
+        CreateLookupTableOutcome::AlreadyExists,
+    );
+}
+
+#[tokio::test]
+async fn test_create_lookup_table_idempotent_with_different_authority() {
+    let mut context = setup_test_context().await;
+
+    let test_recent_slot = 123;
+    context.warp_to_slot(test_recent_slot).unwrap();
+
+    let authority_address = Pubkey::new_unique();
+    let (create_ix, lookup_table_address) = create_lookup_table_checked(
+        authority_address,
+        context.payer.pubkey(),
+        test_recent_slot,
+        CreateLookupTableMode::Idempotent,
+    );
+
+    let existing_table = new_address_lookup_table(Some(Pubkey::new_unique()), 0);
+    add_lookup_table_account(&mut context, lookup_table_address, existing_table).await;
+
+    assert_ix_error(
+        &mut context,
+        create_ix,
+        None,
+        // [Core BPF]: TODO: Should be `ProgramError::IncorrectAuthority`
+        // See https://github.com/solana-labs/solana/pull/35113
+        // InstructionError::IncorrectAuthority,
+        InstructionError::Custom(0),
+    )
+    .await;
+}
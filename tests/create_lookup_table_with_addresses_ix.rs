@@ -0,0 +1,147 @@
+#![cfg(feature = "test-sbf")]
+
+use {
+    assert_matches::assert_matches,
+    common::{assert_ix_error, setup_test_context},
+    solana_program_test::*,
+    solana_programs_address_lookup_table::{
+        instruction::{create_lookup_table, create_lookup_table_with_addresses},
+        state::{AddressLookupTable, LOOKUP_TABLE_META_SIZE},
+    },
+    solana_sdk::{
+        clock::Slot,
+        instruction::InstructionError,
+        pubkey::{Pubkey, PUBKEY_BYTES},
+        rent::Rent,
+        signature::Signer,
+        transaction::Transaction,
+    },
+};
+
+mod common;
+
+#[tokio::test]
+async fn test_create_lookup_table_with_addresses() {
+    let mut context = setup_test_context().await;
+
+    let test_recent_slot = 123;
+    context.warp_to_slot(test_recent_slot).unwrap();
+
+    let client = &mut context.banks_client;
+    let payer = &context.payer;
+    let recent_blockhash = context.last_blockhash;
+    let authority_address = Pubkey::new_unique();
+    let addresses: Vec<Pubkey> = (0..10).map(|_| Pubkey::new_unique()).collect();
+    let (create_ix, lookup_table_address) = create_lookup_table_with_addresses(
+        authority_address,
+        payer.pubkey(),
+        test_recent_slot,
+        addresses.clone(),
+    );
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[create_ix],
+        Some(&payer.pubkey()),
+        &[payer],
+        recent_blockhash,
+    );
+
+    assert_matches!(client.process_transaction(transaction).await, Ok(()));
+    let lookup_table_account = client
+        .get_account(lookup_table_address)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let expected_data_len = LOOKUP_TABLE_META_SIZE + addresses.len() * PUBKEY_BYTES;
+    assert_eq!(
+        lookup_table_account.owner,
+        solana_programs_address_lookup_table::id()
+    );
+    assert_eq!(lookup_table_account.data.len(), expected_data_len);
+    assert_eq!(
+        lookup_table_account.lamports,
+        Rent::default().minimum_balance(expected_data_len)
+    );
+
+    let lookup_table = AddressLookupTable::deserialize(&lookup_table_account.data).unwrap();
+    assert_eq!(lookup_table.meta.deactivation_slot, Slot::MAX);
+    assert_eq!(lookup_table.meta.authority, Some(authority_address));
+    assert_eq!(lookup_table.meta.last_extended_slot, test_recent_slot);
+    assert_eq!(lookup_table.meta.last_extended_slot_start_index, 0);
+    assert_eq!(lookup_table.addresses.to_vec(), addresses);
+
+    // Initial addresses can't be used for lookups until the next slot
+    assert_eq!(
+        lookup_table.get_active_addresses_len(test_recent_slot),
+        Ok(0)
+    );
+    assert_eq!(
+        lookup_table.get_active_addresses_len(test_recent_slot + 1),
+        Ok(addresses.len())
+    );
+}
+
+#[tokio::test]
+async fn test_create_lookup_table_with_no_addresses() {
+    let mut context = setup_test_context().await;
+
+    let test_recent_slot = 123;
+    context.warp_to_slot(test_recent_slot).unwrap();
+
+    let payer = &context.payer;
+    let authority_address = Pubkey::new_unique();
+
+    let ix = create_lookup_table_with_addresses(
+        authority_address,
+        payer.pubkey(),
+        test_recent_slot,
+        vec![],
+    )
+    .0;
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        None,
+        InstructionError::InvalidInstructionData,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_create_lookup_table_with_addresses_already_exists() {
+    let mut context = setup_test_context().await;
+
+    let test_recent_slot = 123;
+    context.warp_to_slot(test_recent_slot).unwrap();
+
+    let client = &mut context.banks_client;
+    let payer = &context.payer;
+    let recent_blockhash = context.last_blockhash;
+    let authority_address = Pubkey::new_unique();
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[create_lookup_table(authority_address, payer.pubkey(), test_recent_slot).0],
+        Some(&payer.pubkey()),
+        &[payer],
+        recent_blockhash,
+    );
+    assert_matches!(client.process_transaction(transaction).await, Ok(()));
+
+    let ix = create_lookup_table_with_addresses(
+        authority_address,
+        payer.pubkey(),
+        test_recent_slot,
+        vec![Pubkey::new_unique()],
+    )
+    .0;
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        None,
+        InstructionError::AccountAlreadyInitialized,
+    )
+    .await;
+}
@@ -0,0 +1,97 @@
+#![cfg(feature = "test-sbf")]
+
+use {
+    assert_matches::assert_matches,
+    common::{assert_ix_error, get_lookup_table_settings, setup_test_context},
+    solana_program_test::*,
+    solana_programs_address_lookup_table::{
+        instruction::create_lookup_table_with_expiry,
+        state::{
+            AddressLookupTable, LookupTableSettings, LookupTableStatus, LOOKUP_TABLE_META_SIZE,
+        },
+    },
+    solana_sdk::{
+        clock::Slot, instruction::InstructionError, pubkey::Pubkey, signature::Signer,
+        transaction::Transaction,
+    },
+};
+
+mod common;
+
+#[tokio::test]
+async fn test_create_lookup_table_with_expiry() {
+    let mut context = setup_test_context().await;
+
+    let test_recent_slot = 123;
+    context.warp_to_slot(test_recent_slot).unwrap();
+
+    let client = &mut context.banks_client;
+    let payer = &context.payer;
+    let recent_blockhash = context.last_blockhash;
+    let authority_address = Pubkey::new_unique();
+    let deactivation_slot = test_recent_slot + 1_000;
+    let (create_ix, lookup_table_address) = create_lookup_table_with_expiry(
+        authority_address,
+        payer.pubkey(),
+        test_recent_slot,
+        deactivation_slot,
+    );
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[create_ix],
+        Some(&payer.pubkey()),
+        &[payer],
+        recent_blockhash,
+    );
+    assert_matches!(client.process_transaction(transaction).await, Ok(()));
+    let lookup_table_account = client
+        .get_account(lookup_table_address)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(lookup_table_account.data.len(), LOOKUP_TABLE_META_SIZE);
+
+    // The table stays activated until the scheduled deactivation is applied
+    let lookup_table = AddressLookupTable::deserialize(&lookup_table_account.data).unwrap();
+    assert_eq!(lookup_table.meta.authority, Some(authority_address));
+    assert_eq!(lookup_table.meta.deactivation_slot, Slot::MAX);
+    assert!(lookup_table.meta.has_settings());
+    assert!(lookup_table.addresses.is_empty());
+    assert_eq!(
+        lookup_table.meta.status(deactivation_slot),
+        LookupTableStatus::Activated
+    );
+    assert_eq!(
+        get_lookup_table_settings(&mut context, lookup_table_address).await,
+        LookupTableSettings {
+            scheduled_deactivation_slot: Some(deactivation_slot),
+            ..LookupTableSettings::new(lookup_table_address)
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_create_lookup_table_with_expiry_not_in_future() {
+    let mut context = setup_test_context().await;
+
+    let test_recent_slot = 123;
+    context.warp_to_slot(test_recent_slot).unwrap();
+
+    let authority_address = Pubkey::new_unique();
+    for deactivation_slot in [0, test_recent_slot] {
+        let (create_ix, _) = create_lookup_table_with_expiry(
+            authority_address,
+            context.payer.pubkey(),
+            test_recent_slot,
+            deactivation_slot,
+        );
+
+        assert_ix_error(
+            &mut context,
+            create_ix,
+            None,
+            InstructionError::InvalidInstructionData,
+        )
+        .await;
+    }
+}
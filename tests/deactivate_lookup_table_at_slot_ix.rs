@@ -0,0 +1,431 @@
+#![cfg(feature = "test-sbf")]
+
+use {
+    common::{
+        add_lookup_table_account, add_lookup_table_account_with_settings, assert_ix_error,
+        get_lookup_table_settings, new_address_lookup_table, process_ix, process_ix_with_signers,
+        setup_test_context,
+    },
+    solana_program_test::*,
+    solana_programs_address_lookup_table::{
+        instruction::{
+            close_lookup_table, create_lookup_table, deactivate_lookup_table_at_slot,
+            deactivate_scheduled_lookup_table, derive_lookup_table_settings_address,
+            extend_lookup_table, freeze_lookup_table,
+        },
+        state::{AddressLookupTable, LookupTableSettings, LookupTableStatus},
+    },
+    solana_sdk::{
+        account::Account,
+        address_lookup_table::state::{
+            AddressLookupTable as RuntimeAddressLookupTable,
+            LookupTableStatus as RuntimeLookupTableStatus,
+        },
+        clock::{Clock, Slot},
+        instruction::{AccountMeta, Instruction, InstructionError},
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        slot_hashes::SlotHashes,
+    },
+};
+
+mod common;
+
+async fn add_scheduled_lookup_table_account(
+    context: &mut ProgramTestContext,
+    authority: &Keypair,
+    scheduled_deactivation_slot: Slot,
+) -> Pubkey {
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 10);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account_with_settings(
+        context,
+        lookup_table_address,
+        initialized_table,
+        LookupTableSettings {
+            scheduled_deactivation_slot: Some(scheduled_deactivation_slot),
+            ..LookupTableSettings::new(lookup_table_address)
+        },
+    )
+    .await;
+    lookup_table_address
+}
+
+/// Appends the settings account of the lookup table to an instruction
+fn with_settings_account(mut ix: Instruction, lookup_table_address: Pubkey) -> Instruction {
+    let (settings_address, _) = derive_lookup_table_settings_address(&lookup_table_address);
+    ix.accounts.push(AccountMeta::new(settings_address, false));
+    ix
+}
+
+/// Returns the status of the table as the runtime sees it when loading
+/// addresses
+async fn get_runtime_status(
+    context: &mut ProgramTestContext,
+    table_account: &Account,
+) -> RuntimeLookupTableStatus {
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    let slot_hashes = context
+        .banks_client
+        .get_sysvar::<SlotHashes>()
+        .await
+        .unwrap();
+    RuntimeAddressLookupTable::deserialize(&table_account.data)
+        .unwrap()
+        .meta
+        .status(clock.slot, &slot_hashes)
+}
+
+#[tokio::test]
+async fn test_deactivate_lookup_table_at_future_slot() {
+    let mut context = setup_test_context().await;
+    let payer_address = context.payer.pubkey();
+
+    let authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 10);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(
+        &mut context,
+        lookup_table_address,
+        initialized_table.clone(),
+    )
+    .await;
+
+    let ix = deactivate_lookup_table_at_slot(
+        lookup_table_address,
+        authority.pubkey(),
+        Some(payer_address),
+        100,
+    );
+    let table_account = process_ix(&mut context, ix, &authority, lookup_table_address).await;
+    let lookup_table = AddressLookupTable::deserialize(&table_account.data).unwrap();
+
+    // The table stays activated, with the slot stored in its settings
+    assert_eq!(lookup_table.meta.deactivation_slot, Slot::MAX);
+    assert!(lookup_table.meta.has_settings());
+    assert_eq!(lookup_table.addresses, initialized_table.addresses);
+    assert_eq!(
+        get_lookup_table_settings(&mut context, lookup_table_address)
+            .await
+            .scheduled_deactivation_slot,
+        Some(100)
+    );
+
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    assert_eq!(
+        lookup_table.meta.status(clock.slot),
+        LookupTableStatus::Activated
+    );
+    assert_eq!(
+        get_runtime_status(&mut context, &table_account).await,
+        RuntimeLookupTableStatus::Activated
+    );
+}
+
+#[tokio::test]
+async fn test_deactivate_lookup_table_at_current_slot() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 10);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(&mut context, lookup_table_address, initialized_table).await;
+
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    let ix =
+        deactivate_lookup_table_at_slot(lookup_table_address, authority.pubkey(), None, clock.slot);
+    let table_account = process_ix(&mut context, ix, &authority, lookup_table_address).await;
+    let lookup_table = AddressLookupTable::deserialize(&table_account.data).unwrap();
+    assert_eq!(lookup_table.meta.deactivation_slot, clock.slot);
+    assert!(!lookup_table.meta.has_settings());
+}
+
+#[tokio::test]
+async fn test_reschedule_lookup_table_deactivation() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let lookup_table_address =
+        add_scheduled_lookup_table_account(&mut context, &authority, 100).await;
+
+    let ix = deactivate_lookup_table_at_slot(lookup_table_address, authority.pubkey(), None, 50);
+    let table_account = process_ix(&mut context, ix, &authority, lookup_table_address).await;
+    let lookup_table = AddressLookupTable::deserialize(&table_account.data).unwrap();
+    assert_eq!(lookup_table.meta.deactivation_slot, Slot::MAX);
+    assert_eq!(
+        get_lookup_table_settings(&mut context, lookup_table_address)
+            .await
+            .scheduled_deactivation_slot,
+        Some(50)
+    );
+}
+
+#[tokio::test]
+async fn test_deactivate_lookup_table_at_past_slot() {
+    let mut context = setup_test_context().await;
+    context.warp_to_slot(10).unwrap();
+
+    let authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 10);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(&mut context, lookup_table_address, initialized_table).await;
+
+    let ix = deactivate_lookup_table_at_slot(lookup_table_address, authority.pubkey(), None, 5);
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        InstructionError::InvalidInstructionData,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_deactivate_deactivated_lookup_table_at_slot() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let mut initialized_table = new_address_lookup_table(Some(authority.pubkey()), 10);
+    initialized_table.meta.deactivation_slot = 0;
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(&mut context, lookup_table_address, initialized_table).await;
+
+    let ix = deactivate_lookup_table_at_slot(
+        lookup_table_address,
+        authority.pubkey(),
+        Some(context.payer.pubkey()),
+        100,
+    );
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        InstructionError::InvalidArgument,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_deactivate_scheduled_lookup_table() {
+    let mut context = setup_test_context().await;
+
+    let scheduled_deactivation_slot = 5;
+    let deactivation_slot = scheduled_deactivation_slot + 5;
+    context.warp_to_slot(deactivation_slot).unwrap();
+
+    let authority = Keypair::new();
+    let lookup_table_address =
+        add_scheduled_lookup_table_account(&mut context, &authority, scheduled_deactivation_slot)
+            .await;
+
+    // The table remains activated until the crank is processed, even after
+    // the scheduled slot is reached
+    let table_account = context
+        .banks_client
+        .get_account(lookup_table_address)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        get_runtime_status(&mut context, &table_account).await,
+        RuntimeLookupTableStatus::Activated
+    );
+
+    // Anyone can process the crank, without the authority's signature
+    process_ix_with_signers(
+        &mut context,
+        deactivate_scheduled_lookup_table(lookup_table_address),
+        &[],
+    )
+    .await;
+    let table_account = context
+        .banks_client
+        .get_account(lookup_table_address)
+        .await
+        .unwrap()
+        .unwrap();
+    let lookup_table = AddressLookupTable::deserialize(&table_account.data).unwrap();
+    assert_eq!(lookup_table.meta.deactivation_slot, deactivation_slot);
+    assert_eq!(
+        get_lookup_table_settings(&mut context, lookup_table_address)
+            .await
+            .scheduled_deactivation_slot,
+        None
+    );
+    assert_eq!(
+        get_runtime_status(&mut context, &table_account).await,
+        RuntimeLookupTableStatus::Deactivating {
+            remaining_blocks: 512 + 1,
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_deactivate_scheduled_lookup_table_before_scheduled_slot() {
+    let mut context = setup_test_context().await;
+    let payer_address = context.payer.pubkey();
+
+    // The table is created by the program rather than injected, since the
+    // test warps to the scheduled slot afterwards
+    let recent_slot = 10;
+    context.warp_to_slot(recent_slot).unwrap();
+    let authority = Keypair::new();
+    let (create_ix, lookup_table_address) =
+        create_lookup_table(authority.pubkey(), payer_address, recent_slot);
+    process_ix_with_signers(&mut context, create_ix, &[]).await;
+
+    let scheduled_deactivation_slot = recent_slot + 100;
+    process_ix(
+        &mut context,
+        deactivate_lookup_table_at_slot(
+            lookup_table_address,
+            authority.pubkey(),
+            Some(payer_address),
+            scheduled_deactivation_slot,
+        ),
+        &authority,
+        lookup_table_address,
+    )
+    .await;
+
+    // The crank fails until the scheduled slot is reached
+    let ix = deactivate_scheduled_lookup_table(lookup_table_address);
+    assert_ix_error(&mut context, ix, None, InstructionError::InvalidArgument).await;
+
+    let table_account = context
+        .banks_client
+        .get_account(lookup_table_address)
+        .await
+        .unwrap()
+        .unwrap();
+    let lookup_table = AddressLookupTable::deserialize(&table_account.data).unwrap();
+    assert_eq!(lookup_table.meta.deactivation_slot, Slot::MAX);
+    assert_eq!(
+        get_runtime_status(&mut context, &table_account).await,
+        RuntimeLookupTableStatus::Activated
+    );
+    assert_eq!(
+        get_lookup_table_settings(&mut context, lookup_table_address)
+            .await
+            .scheduled_deactivation_slot,
+        Some(scheduled_deactivation_slot)
+    );
+
+    // The crank succeeds once the scheduled slot is reached
+    context.warp_to_slot(scheduled_deactivation_slot).unwrap();
+    process_ix_with_signers(
+        &mut context,
+        deactivate_scheduled_lookup_table(lookup_table_address),
+        &[],
+    )
+    .await;
+    let table_account = context
+        .banks_client
+        .get_account(lookup_table_address)
+        .await
+        .unwrap()
+        .unwrap();
+    let lookup_table = AddressLookupTable::deserialize(&table_account.data).unwrap();
+    assert_eq!(
+        lookup_table.meta.deactivation_slot,
+        scheduled_deactivation_slot
+    );
+}
+
+#[tokio::test]
+async fn test_deactivate_unscheduled_lookup_table() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 10);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account_with_settings(
+        &mut context,
+        lookup_table_address,
+        initialized_table,
+        LookupTableSettings::new(lookup_table_address),
+    )
+    .await;
+
+    let ix = deactivate_scheduled_lookup_table(lookup_table_address);
+
+    assert_ix_error(&mut context, ix, None, InstructionError::InvalidArgument).await;
+}
+
+#[tokio::test]
+async fn test_extend_lookup_table_scheduled_for_deactivation() {
+    let mut context = setup_test_context().await;
+    let payer_address = context.payer.pubkey();
+
+    let authority = Keypair::new();
+    let lookup_table_address =
+        add_scheduled_lookup_table_account(&mut context, &authority, 100).await;
+
+    let new_address = Pubkey::new_unique();
+    let ix = with_settings_account(
+        extend_lookup_table(
+            lookup_table_address,
+            authority.pubkey(),
+            Some(payer_address),
+            vec![new_address],
+        ),
+        lookup_table_address,
+    );
+    let table_account = process_ix(&mut context, ix, &authority, lookup_table_address).await;
+    let lookup_table = AddressLookupTable::deserialize(&table_account.data).unwrap();
+
+    assert_eq!(lookup_table.addresses.len(), 11);
+    assert_eq!(lookup_table.addresses.last(), Some(&new_address));
+    assert_eq!(lookup_table.meta.deactivation_slot, Slot::MAX);
+}
+
+#[tokio::test]
+async fn test_freeze_lookup_table_scheduled_for_deactivation() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let lookup_table_address =
+        add_scheduled_lookup_table_account(&mut context, &authority, 100).await;
+
+    let ix = with_settings_account(
+        freeze_lookup_table(lookup_table_address, authority.pubkey()),
+        lookup_table_address,
+    );
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        InstructionError::InvalidArgument,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_close_lookup_table_scheduled_for_deactivation() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let lookup_table_address =
+        add_scheduled_lookup_table_account(&mut context, &authority, 100).await;
+
+    let ix = with_settings_account(
+        close_lookup_table(
+            lookup_table_address,
+            authority.pubkey(),
+            context.payer.pubkey(),
+        ),
+        lookup_table_address,
+    );
+
+    // The ix should fail because the table hasn't been deactivated yet
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        InstructionError::InvalidArgument,
+    )
+    .await;
+}
@@ -0,0 +1,153 @@
+#![cfg(feature = "test-sbf")]
+
+use {
+    assert_matches::assert_matches,
+    common::{
+        add_lookup_table_account, assert_ix_error, lookup_tables_instruction_error,
+        new_address_lookup_table, setup_test_context,
+    },
+    solana_program_test::*,
+    solana_programs_address_lookup_table::{
+        instruction::deactivate_lookup_tables, state::AddressLookupTable,
+    },
+    solana_sdk::{
+        instruction::InstructionError,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        transaction::Transaction,
+    },
+};
+
+mod common;
+
+#[tokio::test]
+async fn test_deactivate_lookup_tables() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let mut initialized_tables = vec![];
+    let mut lookup_table_addresses = vec![];
+    for num_addresses in [0, 10, 256] {
+        let initialized_table = new_address_lookup_table(Some(authority.pubkey()), num_addresses);
+        let lookup_table_address = Pubkey::new_unique();
+        add_lookup_table_account(
+            &mut context,
+            lookup_table_address,
+            initialized_table.clone(),
+        )
+        .await;
+        initialized_tables.push(initialized_table);
+        lookup_table_addresses.push(lookup_table_address);
+    }
+
+    let client = &mut context.banks_client;
+    let payer = &context.payer;
+    let recent_blockhash = context.last_blockhash;
+    let transaction = Transaction::new_signed_with_payer(
+        &[deactivate_lookup_tables(
+            authority.pubkey(),
+            &lookup_table_addresses,
+        )],
+        Some(&payer.pubkey()),
+        &[payer, &authority],
+        recent_blockhash,
+    );
+
+    assert_matches!(client.process_transaction(transaction).await, Ok(()));
+    for (lookup_table_address, mut initialized_table) in
+        lookup_table_addresses.into_iter().zip(initialized_tables)
+    {
+        let table_account = client
+            .get_account(lookup_table_address)
+            .await
+            .unwrap()
+            .unwrap();
+        let lookup_table = AddressLookupTable::deserialize(&table_account.data).unwrap();
+
+        // Check that only the deactivation slot changed
+        initialized_table.meta.deactivation_slot = 1;
+        assert_eq!(initialized_table, lookup_table);
+    }
+}
+
+#[tokio::test]
+async fn test_deactivate_lookup_tables_with_one_already_deactivated() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 10);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(&mut context, lookup_table_address, initialized_table).await;
+
+    let deactivated_table = {
+        let mut table = new_address_lookup_table(Some(authority.pubkey()), 10);
+        table.meta.deactivation_slot = 0;
+        table
+    };
+    let deactivated_lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(
+        &mut context,
+        deactivated_lookup_table_address,
+        deactivated_table,
+    )
+    .await;
+
+    let ix = deactivate_lookup_tables(
+        authority.pubkey(),
+        &[lookup_table_address, deactivated_lookup_table_address],
+    );
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        lookup_tables_instruction_error(1, ProgramError::InvalidArgument),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_deactivate_lookup_tables_with_wrong_authority() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let wrong_authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 10);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(&mut context, lookup_table_address, initialized_table).await;
+
+    let other_table = new_address_lookup_table(Some(wrong_authority.pubkey()), 10);
+    let other_lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(&mut context, other_lookup_table_address, other_table).await;
+
+    let ix = deactivate_lookup_tables(
+        wrong_authority.pubkey(),
+        &[other_lookup_table_address, lookup_table_address],
+    );
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&wrong_authority),
+        // The table at index 1 belongs to a different authority
+        lookup_tables_instruction_error(1, ProgramError::Custom(0)),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_deactivate_no_lookup_tables() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let ix = deactivate_lookup_tables(authority.pubkey(), &[]);
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        InstructionError::NotEnoughAccountKeys,
+    )
+    .await;
+}
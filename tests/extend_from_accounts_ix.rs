@@ -0,0 +1,186 @@
+#![cfg(feature = "test-sbf")]
+
+use {
+    assert_matches::assert_matches,
+    common::{
+        add_lookup_table_account, assert_ix_error, new_address_lookup_table, process_ix,
+        setup_test_context,
+    },
+    solana_program_test::*,
+    solana_programs_address_lookup_table::{
+        instruction::extend_from_accounts,
+        state::{AddressLookupTable, LOOKUP_TABLE_META_SIZE},
+    },
+    solana_sdk::{
+        clock::Clock,
+        instruction::{AccountMeta, InstructionError},
+        pubkey::{Pubkey, PUBKEY_BYTES},
+        signature::{Keypair, Signer},
+        transaction::Transaction,
+    },
+};
+
+mod common;
+
+#[tokio::test]
+async fn test_extend_from_accounts() {
+    let mut context = setup_test_context().await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    let authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 10);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(
+        &mut context,
+        lookup_table_address,
+        initialized_table.clone(),
+    )
+    .await;
+
+    let new_addresses: Vec<Pubkey> = (0..20).map(|_| Pubkey::new_unique()).collect();
+    let client = &mut context.banks_client;
+    let payer = &context.payer;
+    let recent_blockhash = context.last_blockhash;
+    let transaction = Transaction::new_signed_with_payer(
+        &[extend_from_accounts(
+            lookup_table_address,
+            authority.pubkey(),
+            payer.pubkey(),
+            &new_addresses,
+        )],
+        Some(&payer.pubkey()),
+        &[payer, &authority],
+        recent_blockhash,
+    );
+
+    assert_matches!(client.process_transaction(transaction).await, Ok(()));
+    let table_account = client
+        .get_account(lookup_table_address)
+        .await
+        .unwrap()
+        .unwrap();
+    let expected_data_len = LOOKUP_TABLE_META_SIZE + 30 * PUBKEY_BYTES;
+    assert_eq!(table_account.data.len(), expected_data_len);
+    assert_eq!(
+        table_account.lamports,
+        rent.minimum_balance(expected_data_len)
+    );
+
+    let clock = client.get_sysvar::<Clock>().await.unwrap();
+    let lookup_table = AddressLookupTable::deserialize(&table_account.data).unwrap();
+    let mut expected_addresses = initialized_table.addresses.to_vec();
+    expected_addresses.extend(new_addresses);
+    assert_eq!(lookup_table.addresses.to_vec(), expected_addresses);
+    assert_eq!(lookup_table.meta.last_extended_slot, clock.slot);
+    assert_eq!(lookup_table.meta.last_extended_slot_start_index, 10);
+}
+
+#[tokio::test]
+async fn test_extend_from_no_accounts() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 10);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(&mut context, lookup_table_address, initialized_table).await;
+
+    let ix = extend_from_accounts(
+        lookup_table_address,
+        authority.pubkey(),
+        context.payer.pubkey(),
+        &[],
+    );
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        InstructionError::InvalidInstructionData,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_extend_from_accounts_without_payer() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 10);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(&mut context, lookup_table_address, initialized_table).await;
+
+    let mut ix = extend_from_accounts(
+        lookup_table_address,
+        authority.pubkey(),
+        context.payer.pubkey(),
+        &[],
+    );
+    ix.accounts.truncate(2);
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        InstructionError::NotEnoughAccountKeys,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_extend_from_accounts_with_trailing_accounts() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 10);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(
+        &mut context,
+        lookup_table_address,
+        initialized_table.clone(),
+    )
+    .await;
+
+    // Accounts after the counted accounts aren't appended
+    let new_addresses: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+    let mut ix = extend_from_accounts(
+        lookup_table_address,
+        authority.pubkey(),
+        context.payer.pubkey(),
+        &new_addresses,
+    );
+    ix.accounts
+        .push(AccountMeta::new_readonly(Pubkey::new_unique(), false));
+    let table_account = process_ix(&mut context, ix, &authority, lookup_table_address).await;
+
+    let lookup_table = AddressLookupTable::deserialize(&table_account.data).unwrap();
+    let mut expected_addresses = initialized_table.addresses.to_vec();
+    expected_addresses.extend(new_addresses);
+    assert_eq!(lookup_table.addresses.to_vec(), expected_addresses);
+}
+
+#[tokio::test]
+async fn test_extend_from_missing_accounts() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 10);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(&mut context, lookup_table_address, initialized_table).await;
+
+    let new_addresses: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+    let mut ix = extend_from_accounts(
+        lookup_table_address,
+        authority.pubkey(),
+        context.payer.pubkey(),
+        &new_addresses,
+    );
+    ix.accounts.pop();
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        InstructionError::NotEnoughAccountKeys,
+    )
+    .await;
+}
@@ -0,0 +1,472 @@
+#![cfg(feature = "test-sbf")]
+
+use {
+    assert_matches::assert_matches,
+    common::{
+        add_lookup_table_account, add_lookup_table_account_with_settings, assert_ix_error,
+        get_lookup_table_settings, new_address_lookup_table, process_ix, process_ix_with_signers,
+    },
+    solana_program_test::*,
+    solana_programs_address_lookup_table::{
+        instruction::{
+            close_lookup_table, create_lookup_table_with_extend_hook,
+            derive_lookup_table_settings_address, extend_from_accounts,
+            set_lookup_table_extend_hook, ExtendHookInstruction,
+        },
+        state::{AddressLookupTable, LookupTableSettings, LOOKUP_TABLE_META_SIZE},
+    },
+    solana_sdk::{
+        account::{AccountSharedData, ReadableAccount},
+        account_info::AccountInfo,
+        entrypoint::ProgramResult,
+        instruction::{AccountMeta, Instruction, InstructionError},
+        program_error::ProgramError,
+        pubkey::{Pubkey, PUBKEY_BYTES},
+        signature::{Keypair, Signer},
+        slot_hashes::SlotHashes,
+        system_program,
+        sysvar::Sysvar,
+        transaction::Transaction,
+    },
+};
+
+mod common;
+
+/// Extend hook which only approves addresses of forwarded accounts owned by
+/// the system program.
+fn process_extend_hook(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    input: &[u8],
+) -> ProgramResult {
+    let ExtendHookInstruction::Execute { new_addresses } =
+        bincode::deserialize(input).map_err(|_| ProgramError::InvalidInstructionData)?;
+
+    // Forwarded accounts must not carry any privileges
+    if accounts
+        .iter()
+        .any(|account_info| account_info.is_signer || account_info.is_writable)
+    {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    for new_address in new_addresses {
+        let account_info = accounts
+            .iter()
+            .find(|account_info| account_info.key == &new_address)
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        if account_info.owner != &system_program::id() {
+            return Err(ProgramError::IllegalOwner);
+        }
+    }
+
+    Ok(())
+}
+
+async fn setup_test_context_with_extend_hook(extend_hook: Pubkey) -> ProgramTestContext {
+    let mut program_test = ProgramTest::default();
+    program_test.prefer_bpf(false);
+    program_test.add_program(
+        "solana_programs_address_lookup_table",
+        solana_programs_address_lookup_table::id(),
+        processor!(solana_programs_address_lookup_table::processor::process),
+    );
+    program_test.add_program("extend_hook", extend_hook, processor!(process_extend_hook));
+    program_test.start_with_context().await
+}
+
+async fn add_hooked_lookup_table_account(
+    context: &mut ProgramTestContext,
+    lookup_table_address: Pubkey,
+    address_lookup_table: AddressLookupTable<'static>,
+    extend_hook: Pubkey,
+) -> AccountSharedData {
+    add_lookup_table_account_with_settings(
+        context,
+        lookup_table_address,
+        address_lookup_table,
+        LookupTableSettings {
+            extend_hook: Some(extend_hook),
+            ..LookupTableSettings::new(lookup_table_address)
+        },
+    )
+    .await
+}
+
+/// Extends a table with a settings account, forwarding `extra_accounts` for
+/// the extend hook
+fn extend_from_accounts_with_settings(
+    lookup_table_address: Pubkey,
+    authority_address: Pubkey,
+    payer_address: Pubkey,
+    new_addresses: &[Pubkey],
+    extra_accounts: &[Pubkey],
+) -> Instruction {
+    let mut ix = extend_from_accounts(
+        lookup_table_address,
+        authority_address,
+        payer_address,
+        new_addresses,
+    );
+    let (settings_address, _) = derive_lookup_table_settings_address(&lookup_table_address);
+    ix.accounts
+        .push(AccountMeta::new_readonly(settings_address, false));
+    ix.accounts.extend(
+        extra_accounts
+            .iter()
+            .map(|address| AccountMeta::new_readonly(*address, false)),
+    );
+    ix
+}
+
+#[tokio::test]
+async fn test_extend_lookup_table_with_extend_hook() {
+    let extend_hook = Pubkey::new_unique();
+    let mut context = setup_test_context_with_extend_hook(extend_hook).await;
+    let payer_address = context.payer.pubkey();
+
+    let authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 10);
+    let lookup_table_address = Pubkey::new_unique();
+    add_hooked_lookup_table_account(
+        &mut context,
+        lookup_table_address,
+        initialized_table.clone(),
+        extend_hook,
+    )
+    .await;
+
+    let new_addresses: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+    let table_account = process_ix(
+        &mut context,
+        extend_from_accounts_with_settings(
+            lookup_table_address,
+            authority.pubkey(),
+            payer_address,
+            &new_addresses,
+            &[extend_hook],
+        ),
+        &authority,
+        lookup_table_address,
+    )
+    .await;
+
+    let lookup_table = AddressLookupTable::deserialize(&table_account.data).unwrap();
+    let mut expected_addresses = initialized_table.addresses.to_vec();
+    expected_addresses.extend(new_addresses);
+    assert_eq!(lookup_table.addresses.to_vec(), expected_addresses);
+}
+
+#[tokio::test]
+async fn test_extend_lookup_table_rejected_by_extend_hook() {
+    let extend_hook = Pubkey::new_unique();
+    let mut context = setup_test_context_with_extend_hook(extend_hook).await;
+
+    let authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 10);
+    let lookup_table_address = Pubkey::new_unique();
+    add_hooked_lookup_table_account(
+        &mut context,
+        lookup_table_address,
+        initialized_table,
+        extend_hook,
+    )
+    .await;
+
+    // The lookup table account isn't owned by the system program
+    let ix = extend_from_accounts_with_settings(
+        lookup_table_address,
+        authority.pubkey(),
+        context.payer.pubkey(),
+        &[Pubkey::new_unique(), lookup_table_address],
+        &[extend_hook],
+    );
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        InstructionError::IllegalOwner,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_extend_lookup_table_without_extend_hook_account() {
+    let extend_hook = Pubkey::new_unique();
+    let mut context = setup_test_context_with_extend_hook(extend_hook).await;
+
+    let authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 10);
+    let lookup_table_address = Pubkey::new_unique();
+    add_hooked_lookup_table_account(
+        &mut context,
+        lookup_table_address,
+        initialized_table,
+        extend_hook,
+    )
+    .await;
+
+    let ix = extend_from_accounts_with_settings(
+        lookup_table_address,
+        authority.pubkey(),
+        context.payer.pubkey(),
+        &[Pubkey::new_unique()],
+        &[],
+    );
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        InstructionError::NotEnoughAccountKeys,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_extend_lookup_table_without_settings_account() {
+    let extend_hook = Pubkey::new_unique();
+    let mut context = setup_test_context_with_extend_hook(extend_hook).await;
+
+    let authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 10);
+    let lookup_table_address = Pubkey::new_unique();
+    add_hooked_lookup_table_account(
+        &mut context,
+        lookup_table_address,
+        initialized_table,
+        extend_hook,
+    )
+    .await;
+
+    // The hook is stored in the settings account, so it can't be skipped by
+    // leaving the settings account out
+    let mut ix = extend_from_accounts(
+        lookup_table_address,
+        authority.pubkey(),
+        context.payer.pubkey(),
+        &[Pubkey::new_unique()],
+    );
+    ix.accounts
+        .push(AccountMeta::new_readonly(extend_hook, false));
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        InstructionError::NotEnoughAccountKeys,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_set_lookup_table_extend_hook() {
+    let extend_hook = Pubkey::new_unique();
+    let mut context = setup_test_context_with_extend_hook(extend_hook).await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let payer_address = context.payer.pubkey();
+
+    let authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 10);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(
+        &mut context,
+        lookup_table_address,
+        initialized_table.clone(),
+    )
+    .await;
+
+    // The hook is stored in a new settings account, so the table isn't
+    // resized
+    let table_account = process_ix(
+        &mut context,
+        set_lookup_table_extend_hook(
+            lookup_table_address,
+            authority.pubkey(),
+            Some(payer_address),
+            Some(extend_hook),
+        ),
+        &authority,
+        lookup_table_address,
+    )
+    .await;
+    let expected_data_len = LOOKUP_TABLE_META_SIZE + 10 * PUBKEY_BYTES;
+    assert_eq!(table_account.data.len(), expected_data_len);
+    assert_eq!(
+        table_account.lamports,
+        rent.minimum_balance(expected_data_len)
+    );
+
+    let lookup_table = AddressLookupTable::deserialize(&table_account.data).unwrap();
+    assert!(lookup_table.meta.has_settings());
+    assert_eq!(lookup_table.addresses, initialized_table.addresses);
+    assert_eq!(
+        get_lookup_table_settings(&mut context, lookup_table_address).await,
+        LookupTableSettings {
+            extend_hook: Some(extend_hook),
+            ..LookupTableSettings::new(lookup_table_address)
+        }
+    );
+
+    // Clearing the hook no longer requires the hook program account
+    process_ix(
+        &mut context,
+        set_lookup_table_extend_hook(lookup_table_address, authority.pubkey(), None, None),
+        &authority,
+        lookup_table_address,
+    )
+    .await;
+    let table_account = process_ix(
+        &mut context,
+        extend_from_accounts_with_settings(
+            lookup_table_address,
+            authority.pubkey(),
+            payer_address,
+            &[lookup_table_address],
+            &[],
+        ),
+        &authority,
+        lookup_table_address,
+    )
+    .await;
+    let lookup_table = AddressLookupTable::deserialize(&table_account.data).unwrap();
+    assert_eq!(lookup_table.addresses.len(), 11);
+    assert_eq!(
+        get_lookup_table_settings(&mut context, lookup_table_address)
+            .await
+            .extend_hook,
+        None
+    );
+}
+
+#[tokio::test]
+async fn test_set_lookup_table_extend_hook_with_wrong_authority() {
+    let extend_hook = Pubkey::new_unique();
+    let mut context = setup_test_context_with_extend_hook(extend_hook).await;
+
+    let authority = Keypair::new();
+    let wrong_authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 10);
+    let lookup_table_address = Pubkey::new_unique();
+    add_hooked_lookup_table_account(
+        &mut context,
+        lookup_table_address,
+        initialized_table,
+        extend_hook,
+    )
+    .await;
+
+    let ix =
+        set_lookup_table_extend_hook(lookup_table_address, wrong_authority.pubkey(), None, None);
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&wrong_authority),
+        // [Core BPF]: TODO: Should be `ProgramError::IncorrectAuthority`
+        // See https://github.com/solana-labs/solana/pull/35113
+        // InstructionError::IncorrectAuthority,
+        InstructionError::Custom(0),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_create_lookup_table_with_extend_hook() {
+    let extend_hook = Pubkey::new_unique();
+    let mut context = setup_test_context_with_extend_hook(extend_hook).await;
+
+    let test_recent_slot = 123;
+    context.warp_to_slot(test_recent_slot).unwrap();
+
+    let client = &mut context.banks_client;
+    let payer = &context.payer;
+    let recent_blockhash = context.last_blockhash;
+    let authority_address = Pubkey::new_unique();
+    let (create_ix, lookup_table_address) = create_lookup_table_with_extend_hook(
+        authority_address,
+        payer.pubkey(),
+        test_recent_slot,
+        extend_hook,
+    );
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[create_ix],
+        Some(&payer.pubkey()),
+        &[payer],
+        recent_blockhash,
+    );
+    assert_matches!(client.process_transaction(transaction).await, Ok(()));
+    let lookup_table_account = client
+        .get_account(lookup_table_address)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(lookup_table_account.data.len(), LOOKUP_TABLE_META_SIZE);
+
+    let lookup_table = AddressLookupTable::deserialize(&lookup_table_account.data).unwrap();
+    assert_eq!(lookup_table.meta.authority, Some(authority_address));
+    assert!(lookup_table.meta.has_settings());
+    assert!(lookup_table.addresses.is_empty());
+    assert_eq!(
+        get_lookup_table_settings(&mut context, lookup_table_address).await,
+        LookupTableSettings {
+            extend_hook: Some(extend_hook),
+            ..LookupTableSettings::new(lookup_table_address)
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_close_lookup_table_with_settings() {
+    let extend_hook = Pubkey::new_unique();
+    let mut context = setup_test_context_with_extend_hook(extend_hook).await;
+    context
+        .warp_to_slot(SlotHashes::size_of() as u64 + 1)
+        .unwrap();
+
+    let authority = Keypair::new();
+    let initialized_table = {
+        let mut table = new_address_lookup_table(Some(authority.pubkey()), 0);
+        table.meta.deactivation_slot = 0;
+        table
+    };
+    let lookup_table_address = Pubkey::new_unique();
+    let table_account = add_hooked_lookup_table_account(
+        &mut context,
+        lookup_table_address,
+        initialized_table,
+        extend_hook,
+    )
+    .await;
+    let (settings_address, _) = derive_lookup_table_settings_address(&lookup_table_address);
+    let settings_lamports = context
+        .banks_client
+        .get_account(settings_address)
+        .await
+        .unwrap()
+        .unwrap()
+        .lamports;
+
+    let recipient = Pubkey::new_unique();
+    let mut ix = close_lookup_table(lookup_table_address, authority.pubkey(), recipient);
+    ix.accounts.push(AccountMeta::new(settings_address, false));
+    process_ix_with_signers(&mut context, ix, &[&authority]).await;
+
+    let client = &mut context.banks_client;
+    assert!(client
+        .get_account(lookup_table_address)
+        .await
+        .unwrap()
+        .is_none());
+    assert!(client
+        .get_account(settings_address)
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        client.get_balance(recipient).await.unwrap(),
+        table_account.lamports() + settings_lamports
+    );
+}
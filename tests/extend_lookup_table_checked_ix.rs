@@ -0,0 +1,92 @@
+#![cfg(feature = "test-sbf")]
+
+use {
+    assert_matches::assert_matches,
+    common::{
+        add_lookup_table_account, assert_ix_error, new_address_lookup_table, setup_test_context,
+    },
+    solana_program_test::*,
+    solana_programs_address_lookup_table::{
+        error::AddressLookupError, instruction::extend_lookup_table_checked,
+        state::AddressLookupTable,
+    },
+    solana_sdk::{
+        instruction::InstructionError,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        transaction::Transaction,
+    },
+};
+
+mod common;
+
+#[tokio::test]
+async fn test_extend_lookup_table_checked() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 10);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(
+        &mut context,
+        lookup_table_address,
+        initialized_table.clone(),
+    )
+    .await;
+
+    let new_addresses: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+    let client = &mut context.banks_client;
+    let payer = &context.payer;
+    let recent_blockhash = context.last_blockhash;
+    let transaction = Transaction::new_signed_with_payer(
+        &[extend_lookup_table_checked(
+            lookup_table_address,
+            authority.pubkey(),
+            Some(payer.pubkey()),
+            new_addresses.clone(),
+            10,
+        )],
+        Some(&payer.pubkey()),
+        &[payer, &authority],
+        recent_blockhash,
+    );
+
+    assert_matches!(client.process_transaction(transaction).await, Ok(()));
+    let table_account = client
+        .get_account(lookup_table_address)
+        .await
+        .unwrap()
+        .unwrap();
+    let lookup_table = AddressLookupTable::deserialize(&table_account.data).unwrap();
+    let mut expected_addresses = initialized_table.addresses.to_vec();
+    expected_addresses.extend(new_addresses);
+    assert_eq!(lookup_table.addresses.to_vec(), expected_addresses);
+}
+
+#[tokio::test]
+async fn test_extend_lookup_table_checked_with_start_index_mismatch() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 10);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(&mut context, lookup_table_address, initialized_table).await;
+
+    for expected_start_index in [0, 9, 11] {
+        let ix = extend_lookup_table_checked(
+            lookup_table_address,
+            authority.pubkey(),
+            Some(context.payer.pubkey()),
+            vec![Pubkey::new_unique()],
+            expected_start_index,
+        );
+
+        assert_ix_error(
+            &mut context,
+            ix,
+            Some(&authority),
+            InstructionError::Custom(AddressLookupError::StartIndexMismatch as u32),
+        )
+        .await;
+    }
+}
@@ -0,0 +1,100 @@
+#![cfg(feature = "test-sbf")]
+
+use {
+    common::{
+        add_lookup_table_account, assert_ix_error, new_address_lookup_table, process_ix,
+        setup_test_context,
+    },
+    solana_program_test::*,
+    solana_programs_address_lookup_table::{
+        error::AddressLookupError, instruction::extend_lookup_table_idempotent,
+        state::AddressLookupTable,
+    },
+    solana_sdk::{
+        instruction::InstructionError,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+    },
+};
+
+mod common;
+
+#[tokio::test]
+async fn test_extend_lookup_table_idempotent() {
+    let mut context = setup_test_context().await;
+    let payer_address = context.payer.pubkey();
+
+    let authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 10);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(
+        &mut context,
+        lookup_table_address,
+        initialized_table.clone(),
+    )
+    .await;
+
+    let new_addresses: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+    let ix = extend_lookup_table_idempotent(
+        lookup_table_address,
+        authority.pubkey(),
+        Some(payer_address),
+        new_addresses.clone(),
+        10,
+    );
+
+    // First attempt extends the table
+    let extended_account =
+        process_ix(&mut context, ix.clone(), &authority, lookup_table_address).await;
+    let lookup_table = AddressLookupTable::deserialize(&extended_account.data).unwrap();
+    let mut expected_addresses = initialized_table.addresses.to_vec();
+    expected_addresses.extend(new_addresses);
+    assert_eq!(lookup_table.addresses.to_vec(), expected_addresses);
+
+    // Retried attempt leaves the table unchanged
+    let retried_account = process_ix(&mut context, ix, &authority, lookup_table_address).await;
+    assert_eq!(retried_account, extended_account);
+}
+
+#[tokio::test]
+async fn test_extend_lookup_table_idempotent_with_start_index_mismatch() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 10);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(
+        &mut context,
+        lookup_table_address,
+        initialized_table.clone(),
+    )
+    .await;
+
+    let existing_addresses = initialized_table.addresses.to_vec();
+    for (new_addresses, expected_start_index) in [
+        // Different addresses are stored at the expected indexes
+        (vec![Pubkey::new_unique()], 9),
+        // The new addresses are stored at the end, but not at the expected
+        // indexes
+        (existing_addresses[9..].to_vec(), 8),
+        // The new addresses are stored at the expected indexes, but not at
+        // the end
+        (existing_addresses[5..6].to_vec(), 5),
+    ] {
+        let ix = extend_lookup_table_idempotent(
+            lookup_table_address,
+            authority.pubkey(),
+            Some(context.payer.pubkey()),
+            new_addresses,
+            expected_start_index,
+        );
+
+        assert_ix_error(
+            &mut context,
+            ix,
+            Some(&authority),
+            InstructionError::Custom(AddressLookupError::StartIndexMismatch as u32),
+        )
+        .await;
+    }
+}
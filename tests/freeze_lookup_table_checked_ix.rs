@@ -0,0 +1,104 @@
+#![cfg(feature = "test-sbf")]
+
+use {
+    assert_matches::assert_matches,
+    common::{
+        add_lookup_table_account, assert_ix_error, new_address_lookup_table, setup_test_context,
+    },
+    solana_program_test::*,
+    solana_programs_address_lookup_table::{
+        error::AddressLookupError, instruction::freeze_lookup_table_checked,
+        state::AddressLookupTable,
+    },
+    solana_sdk::{
+        instruction::InstructionError,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        transaction::Transaction,
+    },
+};
+
+mod common;
+
+#[tokio::test]
+async fn test_freeze_lookup_table_checked() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let mut initialized_table = new_address_lookup_table(Some(authority.pubkey()), 10);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(
+        &mut context,
+        lookup_table_address,
+        initialized_table.clone(),
+    )
+    .await;
+
+    let client = &mut context.banks_client;
+    let payer = &context.payer;
+    let recent_blockhash = context.last_blockhash;
+    let transaction = Transaction::new_signed_with_payer(
+        &[freeze_lookup_table_checked(
+            lookup_table_address,
+            authority.pubkey(),
+            &initialized_table.addresses,
+        )],
+        Some(&payer.pubkey()),
+        &[payer, &authority],
+        recent_blockhash,
+    );
+
+    assert_matches!(client.process_transaction(transaction).await, Ok(()));
+    let table_account = client
+        .get_account(lookup_table_address)
+        .await
+        .unwrap()
+        .unwrap();
+    let lookup_table = AddressLookupTable::deserialize(&table_account.data).unwrap();
+
+    // Check that only the authority changed
+    initialized_table.meta.authority = None;
+    assert_eq!(initialized_table, lookup_table);
+}
+
+#[tokio::test]
+async fn test_freeze_lookup_table_checked_with_addresses_mismatch() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 10);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(
+        &mut context,
+        lookup_table_address,
+        initialized_table.clone(),
+    )
+    .await;
+
+    let addresses = initialized_table.addresses.to_vec();
+    let mut reordered_addresses = addresses.clone();
+    reordered_addresses.swap(0, 1);
+    let mut replaced_addresses = addresses.clone();
+    replaced_addresses[9] = Pubkey::new_unique();
+
+    for expected_addresses in [
+        addresses[..9].to_vec(),
+        [addresses.clone(), vec![Pubkey::new_unique()]].concat(),
+        reordered_addresses,
+        replaced_addresses,
+    ] {
+        let ix = freeze_lookup_table_checked(
+            lookup_table_address,
+            authority.pubkey(),
+            &expected_addresses,
+        );
+
+        assert_ix_error(
+            &mut context,
+            ix,
+            Some(&authority),
+            InstructionError::Custom(AddressLookupError::AddressesMismatch as u32),
+        )
+        .await;
+    }
+}
@@ -0,0 +1,245 @@
+#![cfg(feature = "test-sbf")]
+
+use {
+    common::{
+        add_lookup_table_account, add_lookup_table_account_with_settings, assert_ix_error,
+        get_lookup_table_settings, new_address_lookup_table, process_ix, setup_test_context,
+    },
+    solana_program_test::*,
+    solana_programs_address_lookup_table::{
+        instruction::{
+            close_lookup_table, deactivate_lookup_table, derive_lookup_table_settings_address,
+            extend_lookup_table, freeze_lookup_table, freeze_lookup_table_until,
+        },
+        state::{
+            AddressLookupTable, LookupTableAuthorityState, LookupTableSettings,
+            LOOKUP_TABLE_META_SIZE,
+        },
+    },
+    solana_sdk::{
+        clock::{Clock, Slot},
+        instruction::{AccountMeta, Instruction, InstructionError},
+        pubkey::{Pubkey, PUBKEY_BYTES},
+        signature::{Keypair, Signer},
+    },
+};
+
+mod common;
+
+async fn add_lookup_table_frozen_until(
+    context: &mut ProgramTestContext,
+    authority: Pubkey,
+    frozen_until: Slot,
+) -> Pubkey {
+    let initialized_table = new_address_lookup_table(Some(authority), 10);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account_with_settings(
+        context,
+        lookup_table_address,
+        initialized_table,
+        LookupTableSettings {
+            frozen_until: Some(frozen_until),
+            ..LookupTableSettings::new(lookup_table_address)
+        },
+    )
+    .await;
+    lookup_table_address
+}
+
+/// Appends the settings account of the lookup table to an instruction
+fn with_settings_account(mut ix: Instruction, lookup_table_address: Pubkey) -> Instruction {
+    let (settings_address, _) = derive_lookup_table_settings_address(&lookup_table_address);
+    if !ix
+        .accounts
+        .iter()
+        .any(|account_meta| account_meta.pubkey == settings_address)
+    {
+        ix.accounts.push(AccountMeta::new(settings_address, false));
+    }
+    ix
+}
+
+#[tokio::test]
+async fn test_freeze_lookup_table_until() {
+    let mut context = setup_test_context().await;
+    let payer_address = context.payer.pubkey();
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    let authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 10);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(
+        &mut context,
+        lookup_table_address,
+        initialized_table.clone(),
+    )
+    .await;
+
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    let frozen_until = clock.slot + 10;
+    let table_account = process_ix(
+        &mut context,
+        freeze_lookup_table_until(
+            lookup_table_address,
+            authority.pubkey(),
+            Some(payer_address),
+            frozen_until,
+        ),
+        &authority,
+        lookup_table_address,
+    )
+    .await;
+
+    // The slot is stored in a new settings account, so the table isn't
+    // resized
+    let expected_data_len = LOOKUP_TABLE_META_SIZE + 10 * PUBKEY_BYTES;
+    assert_eq!(table_account.data.len(), expected_data_len);
+    assert_eq!(
+        table_account.lamports,
+        rent.minimum_balance(expected_data_len)
+    );
+
+    let lookup_table = AddressLookupTable::deserialize(&table_account.data).unwrap();
+    assert!(lookup_table.meta.has_settings());
+    assert_eq!(lookup_table.addresses, initialized_table.addresses);
+    let settings = get_lookup_table_settings(&mut context, lookup_table_address).await;
+    assert_eq!(
+        lookup_table.meta.authority_state(&settings, clock.slot),
+        LookupTableAuthorityState::FrozenUntil {
+            authority: authority.pubkey(),
+            slot: frozen_until,
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_freeze_lookup_table_until_current_slot() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 10);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(&mut context, lookup_table_address, initialized_table).await;
+
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    let ix = freeze_lookup_table_until(
+        lookup_table_address,
+        authority.pubkey(),
+        Some(context.payer.pubkey()),
+        clock.slot,
+    );
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        InstructionError::InvalidInstructionData,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_lookup_table_frozen_until_slot() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let lookup_table_address =
+        add_lookup_table_frozen_until(&mut context, authority.pubkey(), Slot::MAX).await;
+
+    for ix in [
+        extend_lookup_table(
+            lookup_table_address,
+            authority.pubkey(),
+            Some(context.payer.pubkey()),
+            vec![Pubkey::new_unique()],
+        ),
+        freeze_lookup_table(lookup_table_address, authority.pubkey()),
+        freeze_lookup_table_until(lookup_table_address, authority.pubkey(), None, Slot::MAX),
+        deactivate_lookup_table(lookup_table_address, authority.pubkey()),
+        close_lookup_table(
+            lookup_table_address,
+            authority.pubkey(),
+            context.payer.pubkey(),
+        ),
+    ] {
+        assert_ix_error(
+            &mut context,
+            with_settings_account(ix, lookup_table_address),
+            Some(&authority),
+            // [Core BPF]: TODO: Should be `ProgramError::Immutable`
+            // See https://github.com/solana-labs/solana/pull/35113
+            // InstructionError::Immutable,
+            InstructionError::Custom(0),
+        )
+        .await;
+    }
+}
+
+#[tokio::test]
+async fn test_lookup_table_authority_regains_control_after_slot() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let frozen_until = 5;
+    context.warp_to_slot(frozen_until).unwrap();
+    let lookup_table_address =
+        add_lookup_table_frozen_until(&mut context, authority.pubkey(), frozen_until).await;
+
+    let payer_address = context.payer.pubkey();
+    let new_address = Pubkey::new_unique();
+    let table_account = process_ix(
+        &mut context,
+        with_settings_account(
+            extend_lookup_table(
+                lookup_table_address,
+                authority.pubkey(),
+                Some(payer_address),
+                vec![new_address],
+            ),
+            lookup_table_address,
+        ),
+        &authority,
+        lookup_table_address,
+    )
+    .await;
+    let lookup_table = AddressLookupTable::deserialize(&table_account.data).unwrap();
+    assert_eq!(lookup_table.addresses.last(), Some(&new_address));
+
+    let table_account = process_ix(
+        &mut context,
+        with_settings_account(
+            freeze_lookup_table(lookup_table_address, authority.pubkey()),
+            lookup_table_address,
+        ),
+        &authority,
+        lookup_table_address,
+    )
+    .await;
+    let lookup_table = AddressLookupTable::deserialize(&table_account.data).unwrap();
+    assert_eq!(lookup_table.meta.authority, None);
+}
+
+#[tokio::test]
+async fn test_lookup_table_frozen_until_slot_without_settings_account() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let lookup_table_address =
+        add_lookup_table_frozen_until(&mut context, authority.pubkey(), Slot::MAX).await;
+
+    // The freeze slot can't be bypassed by leaving the settings account out
+    let ix = extend_lookup_table(
+        lookup_table_address,
+        authority.pubkey(),
+        Some(context.payer.pubkey()),
+        vec![Pubkey::new_unique()],
+    );
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        InstructionError::NotEnoughAccountKeys,
+    )
+    .await;
+}
@@ -0,0 +1,581 @@
+#![cfg(feature = "test-sbf")]
+
+use {
+    common::{
+        add_deactivated_lookup_table_with_funders, add_lookup_table_account, assert_ix_error,
+        get_lookup_table_funders, new_address_lookup_table, new_open_lookup_table,
+        process_ix_with_signers, setup_test_context,
+    },
+    solana_program_test::*,
+    solana_programs_address_lookup_table::{
+        error::AddressLookupError,
+        instruction::{
+            close_lookup_table, close_lookup_table_with_funders,
+            derive_lookup_table_funders_address, extend_lookup_table,
+            extend_lookup_table_with_funders, initialize_lookup_table_funders, ProgramInstruction,
+        },
+        state::{
+            AddressLookupTable, LookupTableExtendMode, LookupTableFunder, LookupTableFunders,
+            LOOKUP_TABLE_FUNDERS_SEED, LOOKUP_TABLE_MAX_FUNDERS, LOOKUP_TABLE_META_SIZE,
+        },
+    },
+    solana_sdk::{
+        account::AccountSharedData,
+        instruction::InstructionError,
+        pubkey::{Pubkey, PUBKEY_BYTES},
+        signature::{Keypair, Signer},
+        system_program,
+    },
+};
+
+mod common;
+
+#[tokio::test]
+async fn test_initialize_lookup_table_funders() {
+    let mut context = setup_test_context().await;
+    let payer_address = context.payer.pubkey();
+
+    let authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 10);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(
+        &mut context,
+        lookup_table_address,
+        initialized_table.clone(),
+    )
+    .await;
+
+    process_ix_with_signers(
+        &mut context,
+        initialize_lookup_table_funders(lookup_table_address, authority.pubkey(), payer_address),
+        &[&authority],
+    )
+    .await;
+
+    let (funders_address, _) = derive_lookup_table_funders_address(&lookup_table_address);
+    let client = &mut context.banks_client;
+    let table_account = client
+        .get_account(lookup_table_address)
+        .await
+        .unwrap()
+        .unwrap();
+    let lookup_table = AddressLookupTable::deserialize(&table_account.data).unwrap();
+    assert!(lookup_table.meta.records_funders());
+    assert_eq!(lookup_table.addresses, initialized_table.addresses);
+    // The flag is stored in the metadata, so the table isn't resized
+    assert_eq!(
+        table_account.data.len(),
+        LOOKUP_TABLE_META_SIZE + 10 * PUBKEY_BYTES
+    );
+
+    let funders_account = client.get_account(funders_address).await.unwrap().unwrap();
+    assert_eq!(
+        funders_account.owner,
+        solana_programs_address_lookup_table::id()
+    );
+    assert_eq!(
+        LookupTableFunders::deserialize(&funders_account.data).unwrap(),
+        LookupTableFunders {
+            lookup_table: lookup_table_address,
+            funders: vec![LookupTableFunder {
+                address: payer_address,
+                lamports: funders_account.lamports,
+            }],
+        }
+    );
+
+    // Funders can only be initialized once
+    context.last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&context.last_blockhash)
+        .await
+        .unwrap();
+    let ix =
+        initialize_lookup_table_funders(lookup_table_address, authority.pubkey(), payer_address);
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        InstructionError::AccountAlreadyInitialized,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_extend_lookup_table_records_funders() {
+    let mut context = setup_test_context().await;
+    let payer_address = context.payer.pubkey();
+
+    let authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 0);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(&mut context, lookup_table_address, initialized_table).await;
+
+    let funder = Keypair::new();
+    let funder_lamports = 1_000_000_000;
+    context.set_account(
+        &funder.pubkey(),
+        &AccountSharedData::new(funder_lamports, 0, &system_program::id()),
+    );
+
+    process_ix_with_signers(
+        &mut context,
+        initialize_lookup_table_funders(lookup_table_address, authority.pubkey(), payer_address),
+        &[&authority],
+    )
+    .await;
+    let initial_funders = get_lookup_table_funders(&mut context, lookup_table_address).await;
+
+    process_ix_with_signers(
+        &mut context,
+        extend_lookup_table_with_funders(
+            lookup_table_address,
+            authority.pubkey(),
+            funder.pubkey(),
+            vec![Pubkey::new_unique(), Pubkey::new_unique()],
+        ),
+        &[&authority, &funder],
+    )
+    .await;
+
+    let funders = get_lookup_table_funders(&mut context, lookup_table_address).await;
+    let funder_account = context
+        .banks_client
+        .get_account(funder.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(funders.funders.len(), 2);
+    assert_eq!(funders.funders[0], initial_funders.funders[0]);
+    assert_eq!(
+        funders.funders[1],
+        LookupTableFunder {
+            address: funder.pubkey(),
+            lamports: funder_lamports - funder_account.lamports,
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_extend_lookup_table_without_funders_account() {
+    let mut context = setup_test_context().await;
+    let payer_address = context.payer.pubkey();
+
+    let authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 0);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(&mut context, lookup_table_address, initialized_table).await;
+
+    process_ix_with_signers(
+        &mut context,
+        initialize_lookup_table_funders(lookup_table_address, authority.pubkey(), payer_address),
+        &[&authority],
+    )
+    .await;
+
+    let mut ix = extend_lookup_table_with_funders(
+        lookup_table_address,
+        authority.pubkey(),
+        payer_address,
+        vec![Pubkey::new_unique()],
+    );
+    ix.accounts.pop();
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        InstructionError::NotEnoughAccountKeys,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_initialize_lookup_table_funders_with_non_canonical_bump_seed() {
+    let mut context = setup_test_context().await;
+    let payer_address = context.payer.pubkey();
+
+    let authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 0);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(&mut context, lookup_table_address, initialized_table).await;
+
+    let (funders_address, bump_seed) = derive_lookup_table_funders_address(&lookup_table_address);
+    let non_canonical_bump_seed = (0..bump_seed)
+        .rev()
+        .find(|bump_seed| {
+            Pubkey::create_program_address(
+                &[
+                    LOOKUP_TABLE_FUNDERS_SEED,
+                    lookup_table_address.as_ref(),
+                    &[*bump_seed],
+                ],
+                &solana_programs_address_lookup_table::id(),
+            )
+            .is_ok()
+        })
+        .unwrap();
+    let non_canonical_funders_address = Pubkey::create_program_address(
+        &[
+            LOOKUP_TABLE_FUNDERS_SEED,
+            lookup_table_address.as_ref(),
+            &[non_canonical_bump_seed],
+        ],
+        &solana_programs_address_lookup_table::id(),
+    )
+    .unwrap();
+
+    let mut ix =
+        initialize_lookup_table_funders(lookup_table_address, authority.pubkey(), payer_address);
+    ix.data = bincode::serialize(&ProgramInstruction::InitializeLookupTableFunders {
+        bump_seed: non_canonical_bump_seed,
+    })
+    .unwrap();
+    ix.accounts
+        .iter_mut()
+        .filter(|account_meta| account_meta.pubkey == funders_address)
+        .for_each(|account_meta| account_meta.pubkey = non_canonical_funders_address);
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        InstructionError::InvalidArgument,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_extend_lookup_table_with_max_funders() {
+    let mut context = setup_test_context().await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    let authority = Keypair::new();
+    let mut initialized_table = new_address_lookup_table(Some(authority.pubkey()), 0);
+    initialized_table.meta.set_records_funders();
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(&mut context, lookup_table_address, initialized_table).await;
+
+    let funder = Keypair::new();
+    let mut lookup_table_funders = LookupTableFunders::new(lookup_table_address);
+    lookup_table_funders.record(funder.pubkey(), 0).unwrap();
+    for _ in 1..LOOKUP_TABLE_MAX_FUNDERS {
+        lookup_table_funders
+            .record(Pubkey::new_unique(), 0)
+            .unwrap();
+    }
+    let mut data = vec![0; lookup_table_funders.serialized_size().unwrap()];
+    lookup_table_funders.overwrite_data(&mut data).unwrap();
+    let mut funders_account = AccountSharedData::new(
+        rent.minimum_balance(data.len()),
+        data.len(),
+        &solana_programs_address_lookup_table::id(),
+    );
+    funders_account.set_data_from_slice(&data);
+    let (funders_address, _) = derive_lookup_table_funders_address(&lookup_table_address);
+    context.set_account(&funders_address, &funders_account);
+    context.set_account(
+        &funder.pubkey(),
+        &AccountSharedData::new(1_000_000_000, 0, &system_program::id()),
+    );
+
+    // Recorded funders can still fund extensions
+    process_ix_with_signers(
+        &mut context,
+        extend_lookup_table_with_funders(
+            lookup_table_address,
+            authority.pubkey(),
+            funder.pubkey(),
+            vec![Pubkey::new_unique()],
+        ),
+        &[&authority, &funder],
+    )
+    .await;
+    let funders = get_lookup_table_funders(&mut context, lookup_table_address).await;
+    assert_eq!(funders.funders.len(), LOOKUP_TABLE_MAX_FUNDERS);
+    assert!(funders.funders[0].lamports > 0);
+
+    // New funders can't be recorded once the table records the maximum
+    let payer_address = context.payer.pubkey();
+    let ix = extend_lookup_table_with_funders(
+        lookup_table_address,
+        authority.pubkey(),
+        payer_address,
+        vec![Pubkey::new_unique()],
+    );
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        InstructionError::Custom(AddressLookupError::TooManyFunders as u32),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_close_lookup_table_refunds_funders() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let recipient = Pubkey::new_unique();
+    let funder_addresses = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let lookup_table_address = add_deactivated_lookup_table_with_funders(
+        &mut context,
+        authority.pubkey(),
+        3_000_000_000,
+        1_000_000_000,
+        &[
+            LookupTableFunder {
+                address: funder_addresses[0],
+                lamports: 1_000_000_000,
+            },
+            LookupTableFunder {
+                address: funder_addresses[1],
+                lamports: 2_000_000_000,
+            },
+        ],
+    )
+    .await;
+
+    process_ix_with_signers(
+        &mut context,
+        close_lookup_table_with_funders(
+            lookup_table_address,
+            authority.pubkey(),
+            recipient,
+            &funder_addresses,
+        ),
+        &[&authority],
+    )
+    .await;
+
+    let (funders_address, _) = derive_lookup_table_funders_address(&lookup_table_address);
+    let client = &mut context.banks_client;
+    assert!(client
+        .get_account(lookup_table_address)
+        .await
+        .unwrap()
+        .is_none());
+    assert!(client.get_account(funders_address).await.unwrap().is_none());
+
+    // Contributions are refunded in full and the recipient receives the rest
+    for (address, expected_lamports) in [
+        (funder_addresses[0], 1_000_000_000),
+        (funder_addresses[1], 2_000_000_000),
+        (recipient, 1_000_000_000),
+    ] {
+        assert_eq!(
+            client.get_balance(address).await.unwrap(),
+            expected_lamports
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_close_lookup_table_refunds_funders_pro_rata() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let recipient = Pubkey::new_unique();
+    let funder_addresses = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let lookup_table_address = add_deactivated_lookup_table_with_funders(
+        &mut context,
+        authority.pubkey(),
+        3_000_000_000,
+        1_000_000_000,
+        &[
+            LookupTableFunder {
+                address: funder_addresses[0],
+                lamports: 6_000_000_000,
+            },
+            LookupTableFunder {
+                address: funder_addresses[1],
+                lamports: 2_000_000_000,
+            },
+        ],
+    )
+    .await;
+
+    process_ix_with_signers(
+        &mut context,
+        close_lookup_table_with_funders(
+            lookup_table_address,
+            authority.pubkey(),
+            recipient,
+            &funder_addresses,
+        ),
+        &[&authority],
+    )
+    .await;
+
+    let client = &mut context.banks_client;
+    for (address, expected_lamports) in [
+        (funder_addresses[0], 3_000_000_000),
+        (funder_addresses[1], 1_000_000_000),
+    ] {
+        assert_eq!(
+            client.get_balance(address).await.unwrap(),
+            expected_lamports
+        );
+    }
+    assert!(client.get_account(recipient).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_close_lookup_table_without_funders_account() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let recipient = Pubkey::new_unique();
+    let funder_address = Pubkey::new_unique();
+    let lookup_table_address = add_deactivated_lookup_table_with_funders(
+        &mut context,
+        authority.pubkey(),
+        3_000_000_000,
+        1_000_000_000,
+        &[LookupTableFunder {
+            address: funder_address,
+            lamports: 1_000_000_000,
+        }],
+    )
+    .await;
+
+    let ix = close_lookup_table(lookup_table_address, authority.pubkey(), recipient);
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        InstructionError::NotEnoughAccountKeys,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_close_lookup_table_with_unrefundable_funders() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let recipient = Pubkey::new_unique();
+    let missing_funder = Pubkey::new_unique();
+    let drained_funder = Pubkey::new_unique();
+    let funder = Pubkey::new_unique();
+    let lookup_table_address = add_deactivated_lookup_table_with_funders(
+        &mut context,
+        authority.pubkey(),
+        3_000_000_000,
+        1_000_000_000,
+        &[
+            LookupTableFunder {
+                address: missing_funder,
+                lamports: 1_000_000_000,
+            },
+            LookupTableFunder {
+                address: drained_funder,
+                lamports: 1_000,
+            },
+            LookupTableFunder {
+                address: funder,
+                lamports: 1_000_000_000,
+            },
+        ],
+    )
+    .await;
+
+    // The drained funder wouldn't be rent exempt after its refund
+    process_ix_with_signers(
+        &mut context,
+        close_lookup_table_with_funders(
+            lookup_table_address,
+            authority.pubkey(),
+            recipient,
+            &[drained_funder, funder],
+        ),
+        &[&authority],
+    )
+    .await;
+
+    let client = &mut context.banks_client;
+    assert!(client
+        .get_account(lookup_table_address)
+        .await
+        .unwrap()
+        .is_none());
+    assert!(client.get_account(drained_funder).await.unwrap().is_none());
+
+    // Refunds which can't be paid are sent to the recipient
+    for (address, expected_lamports) in [
+        (missing_funder, 0),
+        (funder, 1_000_000_000),
+        (recipient, 3_000_000_000),
+    ] {
+        assert_eq!(
+            client.get_balance(address).await.unwrap(),
+            expected_lamports
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_extend_open_lookup_table_does_not_record_other_signers() {
+    let mut context = setup_test_context().await;
+    let payer_address = context.payer.pubkey();
+
+    let authority = Keypair::new();
+    let initialized_table =
+        new_open_lookup_table(Some(authority.pubkey()), 0, LookupTableExtendMode::Open);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(&mut context, lookup_table_address, initialized_table).await;
+
+    process_ix_with_signers(
+        &mut context,
+        initialize_lookup_table_funders(lookup_table_address, authority.pubkey(), payer_address),
+        &[&authority],
+    )
+    .await;
+    let initial_funders = get_lookup_table_funders(&mut context, lookup_table_address).await;
+
+    // Extensions by other signers don't use up the table's funder slots
+    for _ in 0..LOOKUP_TABLE_MAX_FUNDERS {
+        let signer = Keypair::new();
+        context.set_account(
+            &signer.pubkey(),
+            &AccountSharedData::new(1_000_000_000, 0, &system_program::id()),
+        );
+        process_ix_with_signers(
+            &mut context,
+            extend_lookup_table(
+                lookup_table_address,
+                signer.pubkey(),
+                Some(signer.pubkey()),
+                vec![Pubkey::new_unique()],
+            ),
+            &[&signer],
+        )
+        .await;
+    }
+    assert_eq!(
+        get_lookup_table_funders(&mut context, lookup_table_address).await,
+        initial_funders
+    );
+
+    // Extensions by the authority are still recorded
+    let funder = Keypair::new();
+    context.set_account(
+        &funder.pubkey(),
+        &AccountSharedData::new(1_000_000_000, 0, &system_program::id()),
+    );
+    process_ix_with_signers(
+        &mut context,
+        extend_lookup_table_with_funders(
+            lookup_table_address,
+            authority.pubkey(),
+            funder.pubkey(),
+            vec![Pubkey::new_unique()],
+        ),
+        &[&authority, &funder],
+    )
+    .await;
+    let funders = get_lookup_table_funders(&mut context, lookup_table_address).await;
+    assert_eq!(funders.funders.len(), 2);
+    assert_eq!(funders.funders[1].address, funder.pubkey());
+}
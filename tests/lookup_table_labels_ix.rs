@@ -0,0 +1,382 @@
+#![cfg(feature = "test-sbf")]
+
+use {
+    common::{
+        add_lookup_table_account, assert_ix_error, get_lookup_table_labels,
+        new_address_lookup_table, process_ix_with_signers, setup_test_context,
+    },
+    solana_program_test::*,
+    solana_programs_address_lookup_table::{
+        error::AddressLookupError,
+        instruction::{
+            clear_lookup_table_label, close_lookup_table_labels,
+            derive_lookup_table_labels_address, initialize_lookup_table_labels,
+            set_lookup_table_description, set_lookup_table_label,
+        },
+        state::{LookupTableLabels, LOOKUP_TABLE_MAX_DESCRIPTION_LEN, LOOKUP_TABLE_MAX_LABEL_LEN},
+    },
+    solana_sdk::{
+        account::AccountSharedData,
+        instruction::InstructionError,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        system_program,
+    },
+};
+
+mod common;
+
+/// Adds a table with 10 addresses and initializes its labels account
+async fn add_labeled_lookup_table(
+    context: &mut ProgramTestContext,
+    authority: &Keypair,
+    frozen: bool,
+) -> (Pubkey, Vec<Pubkey>) {
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 10);
+    let addresses = initialized_table.addresses.to_vec();
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(context, lookup_table_address, initialized_table.clone()).await;
+
+    let payer_address = context.payer.pubkey();
+    process_ix_with_signers(
+        context,
+        initialize_lookup_table_labels(lookup_table_address, authority.pubkey(), payer_address),
+        &[authority],
+    )
+    .await;
+
+    if frozen {
+        let mut frozen_table = initialized_table;
+        frozen_table.meta.authority = None;
+        add_lookup_table_account(context, lookup_table_address, frozen_table).await;
+    }
+
+    (lookup_table_address, addresses)
+}
+
+#[tokio::test]
+async fn test_set_lookup_table_labels() {
+    let mut context = setup_test_context().await;
+    let payer_address = context.payer.pubkey();
+
+    let authority = Keypair::new();
+    let (lookup_table_address, addresses) =
+        add_labeled_lookup_table(&mut context, &authority, false).await;
+
+    let labels = get_lookup_table_labels(&mut context, lookup_table_address).await;
+    assert_eq!(
+        labels,
+        LookupTableLabels::new(lookup_table_address, authority.pubkey())
+    );
+
+    for ix in [
+        set_lookup_table_label(
+            lookup_table_address,
+            authority.pubkey(),
+            Some(payer_address),
+            3,
+            "usdc mint".to_string(),
+        ),
+        set_lookup_table_label(
+            lookup_table_address,
+            authority.pubkey(),
+            Some(payer_address),
+            0,
+            "token program".to_string(),
+        ),
+        set_lookup_table_description(
+            lookup_table_address,
+            authority.pubkey(),
+            Some(payer_address),
+            "Swap accounts".to_string(),
+        ),
+    ] {
+        process_ix_with_signers(&mut context, ix, &[&authority]).await;
+    }
+
+    let labels = get_lookup_table_labels(&mut context, lookup_table_address).await;
+    assert_eq!(labels.description, "Swap accounts");
+    let joined = labels.join(&addresses);
+    assert_eq!(joined.len(), 10);
+    assert_eq!(joined[0], (addresses[0], Some("token program")));
+    assert_eq!(joined[3], (addresses[3], Some("usdc mint")));
+    assert_eq!(joined[5], (addresses[5], None));
+
+    // Clear one of the labels, returning the freed lamports to the recipient
+    let (labels_address, _) = derive_lookup_table_labels_address(&lookup_table_address);
+    let labels_lamports = context
+        .banks_client
+        .get_balance(labels_address)
+        .await
+        .unwrap();
+    let recipient = Pubkey::new_unique();
+    let recipient_lamports = 1_000_000_000;
+    context.set_account(
+        &recipient,
+        &AccountSharedData::new(recipient_lamports, 0, &system_program::id()),
+    );
+    process_ix_with_signers(
+        &mut context,
+        clear_lookup_table_label(lookup_table_address, authority.pubkey(), recipient, 3),
+        &[&authority],
+    )
+    .await;
+    let labels = get_lookup_table_labels(&mut context, lookup_table_address).await;
+    assert_eq!(labels.label(3), None);
+    assert_eq!(labels.label(0), Some("token program"));
+
+    let freed_lamports =
+        context.banks_client.get_balance(recipient).await.unwrap() - recipient_lamports;
+    assert!(freed_lamports > 0);
+    assert_eq!(
+        context
+            .banks_client
+            .get_balance(labels_address)
+            .await
+            .unwrap(),
+        labels_lamports - freed_lamports
+    );
+
+    // Clearing a missing label fails
+    context.last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&context.last_blockhash)
+        .await
+        .unwrap();
+    let ix = clear_lookup_table_label(lookup_table_address, authority.pubkey(), recipient, 3);
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        InstructionError::InvalidArgument,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_set_lookup_table_label_with_invalid_index() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let (lookup_table_address, _) = add_labeled_lookup_table(&mut context, &authority, false).await;
+
+    let ix = set_lookup_table_label(
+        lookup_table_address,
+        authority.pubkey(),
+        Some(context.payer.pubkey()),
+        10,
+        "missing".to_string(),
+    );
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        InstructionError::Custom(AddressLookupError::InvalidLookupIndex as u32),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_set_lookup_table_label_too_long() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let (lookup_table_address, _) = add_labeled_lookup_table(&mut context, &authority, false).await;
+
+    let ix = set_lookup_table_label(
+        lookup_table_address,
+        authority.pubkey(),
+        Some(context.payer.pubkey()),
+        0,
+        "a".repeat(LOOKUP_TABLE_MAX_LABEL_LEN + 1),
+    );
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        InstructionError::InvalidInstructionData,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_set_lookup_table_label_without_payer() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let (lookup_table_address, _) = add_labeled_lookup_table(&mut context, &authority, false).await;
+
+    let ix = set_lookup_table_label(
+        lookup_table_address,
+        authority.pubkey(),
+        None,
+        0,
+        "token program".to_string(),
+    );
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        InstructionError::NotEnoughAccountKeys,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_set_lookup_table_label_with_wrong_authority() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let (lookup_table_address, _) = add_labeled_lookup_table(&mut context, &authority, false).await;
+
+    let wrong_authority = Keypair::new();
+    let ix = set_lookup_table_label(
+        lookup_table_address,
+        wrong_authority.pubkey(),
+        Some(context.payer.pubkey()),
+        0,
+        "token program".to_string(),
+    );
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&wrong_authority),
+        // [Core BPF]: TODO: Should be `ProgramError::IncorrectAuthority`
+        // See https://github.com/solana-labs/solana/pull/35113
+        // InstructionError::IncorrectAuthority,
+        InstructionError::Custom(0),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_set_frozen_lookup_table_label() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let (lookup_table_address, _) = add_labeled_lookup_table(&mut context, &authority, true).await;
+
+    let ix = set_lookup_table_label(
+        lookup_table_address,
+        authority.pubkey(),
+        Some(context.payer.pubkey()),
+        0,
+        "token program".to_string(),
+    );
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        // [Core BPF]: TODO: Should be `ProgramError::Immutable`
+        // See https://github.com/solana-labs/solana/pull/35113
+        // InstructionError::Immutable,
+        InstructionError::Custom(0),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_set_lookup_table_description_too_long() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let (lookup_table_address, _) = add_labeled_lookup_table(&mut context, &authority, false).await;
+
+    let ix = set_lookup_table_description(
+        lookup_table_address,
+        authority.pubkey(),
+        Some(context.payer.pubkey()),
+        "a".repeat(LOOKUP_TABLE_MAX_DESCRIPTION_LEN + 1),
+    );
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        InstructionError::InvalidInstructionData,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_close_frozen_lookup_table_labels() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let (lookup_table_address, _) = add_labeled_lookup_table(&mut context, &authority, true).await;
+    let (labels_address, _) = derive_lookup_table_labels_address(&lookup_table_address);
+    let labels_lamports = context
+        .banks_client
+        .get_balance(labels_address)
+        .await
+        .unwrap();
+
+    let recipient = Pubkey::new_unique();
+    process_ix_with_signers(
+        &mut context,
+        close_lookup_table_labels(lookup_table_address, authority.pubkey(), recipient),
+        &[&authority],
+    )
+    .await;
+
+    assert!(context
+        .banks_client
+        .get_account(labels_address)
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        context.banks_client.get_balance(recipient).await.unwrap(),
+        labels_lamports
+    );
+}
+
+#[tokio::test]
+async fn test_close_lookup_table_labels_with_wrong_authority() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let (lookup_table_address, _) = add_labeled_lookup_table(&mut context, &authority, false).await;
+
+    let wrong_authority = Keypair::new();
+    let ix = close_lookup_table_labels(
+        lookup_table_address,
+        wrong_authority.pubkey(),
+        Pubkey::new_unique(),
+    );
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&wrong_authority),
+        // [Core BPF]: TODO: Should be `ProgramError::IncorrectAuthority`
+        // See https://github.com/solana-labs/solana/pull/35113
+        // InstructionError::IncorrectAuthority,
+        InstructionError::Custom(0),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_close_lookup_table_labels_to_itself() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let (lookup_table_address, _) = add_labeled_lookup_table(&mut context, &authority, false).await;
+    let (labels_address, _) = derive_lookup_table_labels_address(&lookup_table_address);
+
+    let ix = close_lookup_table_labels(lookup_table_address, authority.pubkey(), labels_address);
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        InstructionError::InvalidArgument,
+    )
+    .await;
+}
@@ -0,0 +1,234 @@
+#![cfg(feature = "test-sbf")]
+
+use {
+    common::{
+        add_registered_lookup_table, assert_ix_error, get_lookup_table_registry,
+        process_ix_with_signers, setup_test_context,
+    },
+    solana_program_test::*,
+    solana_programs_address_lookup_table::{
+        instruction::{
+            close_lookup_table, close_registered_lookup_table, create_lookup_table_with_registry,
+            derive_lookup_table_registry_address, freeze_registered_lookup_table,
+            initialize_lookup_table_registry, ProgramInstruction,
+        },
+        state::{
+            AddressLookupTable, LookupTableRegistry, LOOKUP_TABLE_META_SIZE,
+            LOOKUP_TABLE_REGISTRY_SEED,
+        },
+    },
+    solana_sdk::{
+        instruction::InstructionError,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        slot_hashes::SlotHashes,
+        sysvar::Sysvar,
+    },
+};
+
+mod common;
+
+#[tokio::test]
+async fn test_initialize_lookup_table_registry() {
+    let mut context = setup_test_context().await;
+    let payer_address = context.payer.pubkey();
+
+    let authority = Keypair::new();
+    process_ix_with_signers(
+        &mut context,
+        initialize_lookup_table_registry(authority.pubkey(), payer_address),
+        &[&authority],
+    )
+    .await;
+
+    let (_, bump_seed) = derive_lookup_table_registry_address(&authority.pubkey());
+    assert_eq!(
+        get_lookup_table_registry(&mut context, authority.pubkey()).await,
+        LookupTableRegistry::new(authority.pubkey(), bump_seed)
+    );
+
+    // Registries can only be initialized once
+    context.last_blockhash = context
+        .banks_client
+        .get_new_latest_blockhash(&context.last_blockhash)
+        .await
+        .unwrap();
+    let ix = initialize_lookup_table_registry(authority.pubkey(), payer_address);
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        InstructionError::AccountAlreadyInitialized,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_initialize_lookup_table_registry_with_non_canonical_bump_seed() {
+    let mut context = setup_test_context().await;
+    let payer_address = context.payer.pubkey();
+
+    let authority = Keypair::new();
+    let (_, bump_seed) = derive_lookup_table_registry_address(&authority.pubkey());
+    let (non_canonical_registry_address, non_canonical_bump_seed) = (0..bump_seed)
+        .rev()
+        .find_map(|bump_seed| {
+            Pubkey::create_program_address(
+                &[
+                    LOOKUP_TABLE_REGISTRY_SEED,
+                    authority.pubkey().as_ref(),
+                    &[bump_seed],
+                ],
+                &solana_programs_address_lookup_table::id(),
+            )
+            .ok()
+            .map(|address| (address, bump_seed))
+        })
+        .unwrap();
+
+    let mut ix = initialize_lookup_table_registry(authority.pubkey(), payer_address);
+    ix.data = bincode::serialize(&ProgramInstruction::InitializeLookupTableRegistry {
+        bump_seed: non_canonical_bump_seed,
+    })
+    .unwrap();
+    ix.accounts[0].pubkey = non_canonical_registry_address;
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        InstructionError::InvalidArgument,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_create_registered_lookup_tables() {
+    let mut context = setup_test_context().await;
+    context.warp_to_slot(123).unwrap();
+    let payer_address = context.payer.pubkey();
+
+    let authority = Keypair::new();
+    process_ix_with_signers(
+        &mut context,
+        initialize_lookup_table_registry(authority.pubkey(), payer_address),
+        &[&authority],
+    )
+    .await;
+
+    let mut lookup_table_addresses = vec![];
+    for recent_slot in [123, 122] {
+        let (ix, lookup_table_address) =
+            create_lookup_table_with_registry(authority.pubkey(), payer_address, recent_slot);
+        process_ix_with_signers(&mut context, ix, &[]).await;
+        lookup_table_addresses.push(lookup_table_address);
+    }
+
+    let registry = get_lookup_table_registry(&mut context, authority.pubkey()).await;
+    assert_eq!(registry.lookup_tables, lookup_table_addresses);
+
+    let table_account = context
+        .banks_client
+        .get_account(lookup_table_addresses[0])
+        .await
+        .unwrap()
+        .unwrap();
+    let lookup_table = AddressLookupTable::deserialize(&table_account.data).unwrap();
+    assert_eq!(lookup_table.meta.authority, Some(authority.pubkey()));
+    assert!(lookup_table.meta.is_registered());
+    // The flag is stored in the metadata, so the table keeps its layout
+    assert_eq!(table_account.data.len(), LOOKUP_TABLE_META_SIZE);
+}
+
+#[tokio::test]
+async fn test_create_lookup_table_with_wrong_registry() {
+    let mut context = setup_test_context().await;
+    context.warp_to_slot(123).unwrap();
+    let payer_address = context.payer.pubkey();
+
+    let other_authority = Keypair::new();
+    process_ix_with_signers(
+        &mut context,
+        initialize_lookup_table_registry(other_authority.pubkey(), payer_address),
+        &[&other_authority],
+    )
+    .await;
+
+    let authority_address = Pubkey::new_unique();
+    let (mut ix, _) = create_lookup_table_with_registry(authority_address, payer_address, 123);
+    let (other_registry_address, _) =
+        derive_lookup_table_registry_address(&other_authority.pubkey());
+    ix.accounts[4].pubkey = other_registry_address;
+
+    assert_ix_error(&mut context, ix, None, InstructionError::InvalidArgument).await;
+}
+
+#[tokio::test]
+async fn test_close_registered_lookup_table() {
+    let mut context = setup_test_context().await;
+    context
+        .warp_to_slot(SlotHashes::size_of() as u64 + 1)
+        .unwrap();
+
+    let authority = Keypair::new();
+    let recipient = Pubkey::new_unique();
+    let lookup_table_address =
+        add_registered_lookup_table(&mut context, authority.pubkey(), Some(0)).await;
+    let initial_registry = get_lookup_table_registry(&mut context, authority.pubkey()).await;
+
+    // The registry account is required to close the table
+    let ix = close_lookup_table(lookup_table_address, authority.pubkey(), recipient);
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        InstructionError::NotEnoughAccountKeys,
+    )
+    .await;
+
+    process_ix_with_signers(
+        &mut context,
+        close_registered_lookup_table(lookup_table_address, authority.pubkey(), recipient),
+        &[&authority],
+    )
+    .await;
+
+    assert!(context
+        .banks_client
+        .get_account(lookup_table_address)
+        .await
+        .unwrap()
+        .is_none());
+    let registry = get_lookup_table_registry(&mut context, authority.pubkey()).await;
+    assert_eq!(registry.lookup_tables, initial_registry.lookup_tables[..1]);
+}
+
+#[tokio::test]
+async fn test_freeze_registered_lookup_table() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let lookup_table_address =
+        add_registered_lookup_table(&mut context, authority.pubkey(), None).await;
+    let initial_registry = get_lookup_table_registry(&mut context, authority.pubkey()).await;
+
+    process_ix_with_signers(
+        &mut context,
+        freeze_registered_lookup_table(lookup_table_address, authority.pubkey()),
+        &[&authority],
+    )
+    .await;
+
+    let table_account = context
+        .banks_client
+        .get_account(lookup_table_address)
+        .await
+        .unwrap()
+        .unwrap();
+    let lookup_table = AddressLookupTable::deserialize(&table_account.data).unwrap();
+    assert_eq!(lookup_table.meta.authority, None);
+    assert!(!lookup_table.meta.is_registered());
+
+    let registry = get_lookup_table_registry(&mut context, authority.pubkey()).await;
+    assert_eq!(registry.lookup_tables, initial_registry.lookup_tables[..1]);
+}
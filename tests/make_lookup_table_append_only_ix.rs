@@ -0,0 +1,178 @@
+#![cfg(feature = "test-sbf")]
+
+use {
+    common::{
+        add_lookup_table_account, assert_ix_error, new_address_lookup_table,
+        new_append_only_lookup_table, process_ix, setup_test_context,
+    },
+    solana_program_test::*,
+    solana_programs_address_lookup_table::{
+        error::AddressLookupError,
+        instruction::{
+            close_lookup_table, deactivate_lookup_table, extend_lookup_table,
+            make_lookup_table_append_only,
+        },
+        state::{AddressLookupTable, LOOKUP_TABLE_META_SIZE},
+    },
+    solana_sdk::{
+        instruction::InstructionError,
+        pubkey::{Pubkey, PUBKEY_BYTES},
+        signature::{Keypair, Signer},
+        slot_hashes::SlotHashes,
+        sysvar::Sysvar,
+    },
+};
+
+mod common;
+
+#[tokio::test]
+async fn test_make_lookup_table_append_only() {
+    let mut context = setup_test_context().await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let payer_address = context.payer.pubkey();
+
+    let authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 10);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(
+        &mut context,
+        lookup_table_address,
+        initialized_table.clone(),
+    )
+    .await;
+
+    // The flag is stored in the metadata, so the table isn't resized
+    let table_account = process_ix(
+        &mut context,
+        make_lookup_table_append_only(lookup_table_address, authority.pubkey()),
+        &authority,
+        lookup_table_address,
+    )
+    .await;
+    let expected_data_len = LOOKUP_TABLE_META_SIZE + 10 * PUBKEY_BYTES;
+    assert_eq!(table_account.data.len(), expected_data_len);
+    assert_eq!(
+        table_account.lamports,
+        rent.minimum_balance(expected_data_len)
+    );
+
+    let lookup_table = AddressLookupTable::deserialize(&table_account.data).unwrap();
+    assert!(lookup_table.meta.is_append_only());
+    assert_eq!(lookup_table.addresses, initialized_table.addresses);
+
+    // Append-only tables can still be extended
+    let new_addresses: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+    let table_account = process_ix(
+        &mut context,
+        extend_lookup_table(
+            lookup_table_address,
+            authority.pubkey(),
+            Some(payer_address),
+            new_addresses.clone(),
+        ),
+        &authority,
+        lookup_table_address,
+    )
+    .await;
+    let lookup_table = AddressLookupTable::deserialize(&table_account.data).unwrap();
+    let mut expected_addresses = initialized_table.addresses.to_vec();
+    expected_addresses.extend(new_addresses);
+    assert!(lookup_table.meta.is_append_only());
+    assert_eq!(lookup_table.addresses.to_vec(), expected_addresses);
+}
+
+#[tokio::test]
+async fn test_make_lookup_table_append_only_twice() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let initialized_table = new_append_only_lookup_table(Some(authority.pubkey()), 10);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(&mut context, lookup_table_address, initialized_table).await;
+
+    let ix = make_lookup_table_append_only(lookup_table_address, authority.pubkey());
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        InstructionError::InvalidArgument,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_make_immutable_lookup_table_append_only() {
+    let mut context = setup_test_context().await;
+
+    let initialized_table = new_address_lookup_table(None, 10);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(&mut context, lookup_table_address, initialized_table).await;
+
+    let authority = Keypair::new();
+    let ix = make_lookup_table_append_only(lookup_table_address, authority.pubkey());
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        // [Core BPF]: TODO: Should be `ProgramError::Immutable`
+        // See https://github.com/solana-labs/solana/pull/35113
+        // InstructionError::Immutable,
+        InstructionError::Custom(0),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_deactivate_append_only_lookup_table() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let initialized_table = new_append_only_lookup_table(Some(authority.pubkey()), 10);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(&mut context, lookup_table_address, initialized_table).await;
+
+    let ix = deactivate_lookup_table(lookup_table_address, authority.pubkey());
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        InstructionError::Custom(AddressLookupError::AppendOnly as u32),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_close_append_only_lookup_table() {
+    let mut context = setup_test_context().await;
+    context
+        .warp_to_slot(SlotHashes::size_of() as u64 + 1)
+        .unwrap();
+
+    // Tables deactivated before being made append-only can't exist, but
+    // closing must be rejected regardless of the deactivation slot.
+    let authority = Keypair::new();
+    let initialized_table = {
+        let mut table = new_append_only_lookup_table(Some(authority.pubkey()), 10);
+        table.meta.deactivation_slot = 0;
+        table
+    };
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(&mut context, lookup_table_address, initialized_table).await;
+
+    let ix = close_lookup_table(
+        lookup_table_address,
+        authority.pubkey(),
+        context.payer.pubkey(),
+    );
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        InstructionError::Custom(AddressLookupError::AppendOnly as u32),
+    )
+    .await;
+}
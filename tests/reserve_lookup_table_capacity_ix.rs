@@ -0,0 +1,249 @@
+#![cfg(feature = "test-sbf")]
+
+use {
+    common::{
+        add_lookup_table_account, assert_ix_error, new_address_lookup_table, process_ix,
+        setup_test_context,
+    },
+    solana_program_test::*,
+    solana_programs_address_lookup_table::{
+        instruction::{extend_lookup_table, reserve_lookup_table_capacity},
+        state::{AddressLookupTable, LOOKUP_TABLE_META_SIZE},
+    },
+    solana_sdk::{
+        clock::Clock,
+        instruction::InstructionError,
+        pubkey::{Pubkey, PUBKEY_BYTES},
+        signature::{Keypair, Signer},
+    },
+};
+
+mod common;
+
+#[tokio::test]
+async fn test_reserve_lookup_table_capacity() {
+    let mut context = setup_test_context().await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let payer_address = context.payer.pubkey();
+
+    let authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 10);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(
+        &mut context,
+        lookup_table_address,
+        initialized_table.clone(),
+    )
+    .await;
+
+    // Reserving capacity only funds the table's rent up front
+    let reserved_data_len = LOOKUP_TABLE_META_SIZE + 20 * PUBKEY_BYTES;
+    let table_account = process_ix(
+        &mut context,
+        reserve_lookup_table_capacity(lookup_table_address, authority.pubkey(), payer_address, 20),
+        &authority,
+        lookup_table_address,
+    )
+    .await;
+    assert_eq!(
+        table_account.data.len(),
+        LOOKUP_TABLE_META_SIZE + 10 * PUBKEY_BYTES
+    );
+    assert_eq!(
+        table_account.lamports,
+        rent.minimum_balance(reserved_data_len)
+    );
+    let lookup_table = AddressLookupTable::deserialize(&table_account.data).unwrap();
+    assert_eq!(lookup_table, initialized_table);
+
+    // The runtime doesn't resolve any addresses which weren't extended
+    let runtime_table = solana_sdk::address_lookup_table::state::AddressLookupTable::deserialize(
+        &table_account.data,
+    )
+    .unwrap();
+    assert_eq!(runtime_table.addresses, initialized_table.addresses);
+
+    // Extending within the reserved capacity doesn't require a payer
+    let new_addresses: Vec<Pubkey> = (0..10).map(|_| Pubkey::new_unique()).collect();
+    let table_account = process_ix(
+        &mut context,
+        extend_lookup_table(
+            lookup_table_address,
+            authority.pubkey(),
+            None,
+            new_addresses.clone(),
+        ),
+        &authority,
+        lookup_table_address,
+    )
+    .await;
+    assert_eq!(table_account.data.len(), reserved_data_len);
+    assert_eq!(
+        table_account.lamports,
+        rent.minimum_balance(reserved_data_len)
+    );
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    let lookup_table = AddressLookupTable::deserialize(&table_account.data).unwrap();
+    let mut expected_addresses = initialized_table.addresses.to_vec();
+    expected_addresses.extend(new_addresses);
+    assert_eq!(lookup_table.meta.last_extended_slot, clock.slot);
+    assert_eq!(lookup_table.meta.last_extended_slot_start_index, 10);
+    assert_eq!(lookup_table.addresses.to_vec(), expected_addresses);
+
+    // Extending past the reserved capacity requires a payer again
+    let new_addresses: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+    let table_account = process_ix(
+        &mut context,
+        extend_lookup_table(
+            lookup_table_address,
+            authority.pubkey(),
+            Some(payer_address),
+            new_addresses.clone(),
+        ),
+        &authority,
+        lookup_table_address,
+    )
+    .await;
+    let extended_data_len = LOOKUP_TABLE_META_SIZE + 25 * PUBKEY_BYTES;
+    assert_eq!(table_account.data.len(), extended_data_len);
+    assert_eq!(
+        table_account.lamports,
+        rent.minimum_balance(extended_data_len)
+    );
+    let lookup_table = AddressLookupTable::deserialize(&table_account.data).unwrap();
+    expected_addresses.extend(new_addresses);
+    assert_eq!(lookup_table.addresses.to_vec(), expected_addresses);
+}
+
+#[tokio::test]
+async fn test_reserve_lookup_table_capacity_not_increased() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 10);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(&mut context, lookup_table_address, initialized_table).await;
+
+    let ix = reserve_lookup_table_capacity(
+        lookup_table_address,
+        authority.pubkey(),
+        context.payer.pubkey(),
+        10,
+    );
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        InstructionError::InvalidInstructionData,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_reserve_lookup_table_capacity_exceeds_max_addresses() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 0);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(&mut context, lookup_table_address, initialized_table).await;
+
+    let ix = reserve_lookup_table_capacity(
+        lookup_table_address,
+        authority.pubkey(),
+        context.payer.pubkey(),
+        257,
+    );
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        InstructionError::InvalidInstructionData,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_reserve_lookup_table_capacity_without_payer() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 0);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(&mut context, lookup_table_address, initialized_table).await;
+
+    let mut ix = reserve_lookup_table_capacity(
+        lookup_table_address,
+        authority.pubkey(),
+        context.payer.pubkey(),
+        10,
+    );
+    ix.accounts.truncate(2);
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        InstructionError::NotEnoughAccountKeys,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_reserve_lookup_table_capacity_with_wrong_authority() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let wrong_authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 0);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(&mut context, lookup_table_address, initialized_table).await;
+
+    let ix = reserve_lookup_table_capacity(
+        lookup_table_address,
+        wrong_authority.pubkey(),
+        context.payer.pubkey(),
+        10,
+    );
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&wrong_authority),
+        // [Core BPF]: TODO: Should be `ProgramError::IncorrectAuthority`
+        // See https://github.com/solana-labs/solana/pull/35113
+        // InstructionError::IncorrectAuthority,
+        InstructionError::Custom(0),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_reserve_immutable_lookup_table_capacity() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(None, 1);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(&mut context, lookup_table_address, initialized_table).await;
+
+    let ix = reserve_lookup_table_capacity(
+        lookup_table_address,
+        authority.pubkey(),
+        context.payer.pubkey(),
+        10,
+    );
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        // [Core BPF]: TODO: Should be `ProgramError::Immutable`
+        // See https://github.com/solana-labs/solana/pull/35113
+        // InstructionError::Immutable,
+        InstructionError::Custom(0),
+    )
+    .await;
+}
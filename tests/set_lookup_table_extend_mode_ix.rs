@@ -0,0 +1,262 @@
+#![cfg(feature = "test-sbf")]
+
+use {
+    common::{
+        add_lookup_table_account, assert_ix_error, new_address_lookup_table, new_open_lookup_table,
+        process_ix, setup_test_context,
+    },
+    solana_program_test::*,
+    solana_programs_address_lookup_table::{
+        instruction::{extend_lookup_table, set_lookup_table_extend_mode},
+        state::{AddressLookupTable, LookupTableExtendMode, LOOKUP_TABLE_META_SIZE},
+    },
+    solana_sdk::{
+        instruction::InstructionError,
+        pubkey::{Pubkey, PUBKEY_BYTES},
+        signature::{Keypair, Signer},
+    },
+};
+
+mod common;
+
+#[tokio::test]
+async fn test_set_lookup_table_extend_mode() {
+    let mut context = setup_test_context().await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    let authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 10);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(
+        &mut context,
+        lookup_table_address,
+        initialized_table.clone(),
+    )
+    .await;
+
+    // The mode is stored in the metadata, so the table isn't resized
+    let table_account = process_ix(
+        &mut context,
+        set_lookup_table_extend_mode(
+            lookup_table_address,
+            authority.pubkey(),
+            LookupTableExtendMode::Open,
+        ),
+        &authority,
+        lookup_table_address,
+    )
+    .await;
+    let expected_data_len = LOOKUP_TABLE_META_SIZE + 10 * PUBKEY_BYTES;
+    assert_eq!(table_account.data.len(), expected_data_len);
+    assert_eq!(
+        table_account.lamports,
+        rent.minimum_balance(expected_data_len)
+    );
+
+    let lookup_table = AddressLookupTable::deserialize(&table_account.data).unwrap();
+    assert_eq!(lookup_table.meta.extend_mode(), LookupTableExtendMode::Open);
+    assert_eq!(lookup_table.addresses, initialized_table.addresses);
+
+    // The authority can restrict extending the table again
+    let table_account = process_ix(
+        &mut context,
+        set_lookup_table_extend_mode(
+            lookup_table_address,
+            authority.pubkey(),
+            LookupTableExtendMode::Authority,
+        ),
+        &authority,
+        lookup_table_address,
+    )
+    .await;
+    let lookup_table = AddressLookupTable::deserialize(&table_account.data).unwrap();
+    assert_eq!(
+        lookup_table.meta.extend_mode(),
+        LookupTableExtendMode::Authority
+    );
+}
+
+#[tokio::test]
+async fn test_extend_open_lookup_table() {
+    let mut context = setup_test_context().await;
+    let payer_address = context.payer.pubkey();
+
+    let authority = Keypair::new();
+    let initialized_table =
+        new_open_lookup_table(Some(authority.pubkey()), 10, LookupTableExtendMode::Open);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(
+        &mut context,
+        lookup_table_address,
+        initialized_table.clone(),
+    )
+    .await;
+
+    // Any signer can extend the table, including with duplicate addresses
+    let extender = Keypair::new();
+    let new_addresses = vec![Pubkey::new_unique(), initialized_table.addresses[0]];
+    let table_account = process_ix(
+        &mut context,
+        extend_lookup_table(
+            lookup_table_address,
+            extender.pubkey(),
+            Some(payer_address),
+            new_addresses.clone(),
+        ),
+        &extender,
+        lookup_table_address,
+    )
+    .await;
+
+    let lookup_table = AddressLookupTable::deserialize(&table_account.data).unwrap();
+    let mut expected_addresses = initialized_table.addresses.to_vec();
+    expected_addresses.extend(new_addresses);
+    assert_eq!(lookup_table.addresses.to_vec(), expected_addresses);
+    assert_eq!(lookup_table.meta.authority, Some(authority.pubkey()));
+}
+
+#[tokio::test]
+async fn test_extend_open_deduplicated_lookup_table() {
+    let mut context = setup_test_context().await;
+    let payer_address = context.payer.pubkey();
+
+    let authority = Keypair::new();
+    let initialized_table = new_open_lookup_table(
+        Some(authority.pubkey()),
+        10,
+        LookupTableExtendMode::OpenDeduplicated,
+    );
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(
+        &mut context,
+        lookup_table_address,
+        initialized_table.clone(),
+    )
+    .await;
+
+    // Addresses already stored in the table or repeated are skipped
+    let extender = Keypair::new();
+    let new_address = Pubkey::new_unique();
+    let table_account = process_ix(
+        &mut context,
+        extend_lookup_table(
+            lookup_table_address,
+            extender.pubkey(),
+            Some(payer_address),
+            vec![new_address, initialized_table.addresses[3], new_address],
+        ),
+        &extender,
+        lookup_table_address,
+    )
+    .await;
+
+    let lookup_table = AddressLookupTable::deserialize(&table_account.data).unwrap();
+    let mut expected_addresses = initialized_table.addresses.to_vec();
+    expected_addresses.push(new_address);
+    assert_eq!(lookup_table.addresses.to_vec(), expected_addresses);
+
+    // Extending with only stored addresses leaves the table unchanged
+    let unchanged_account = process_ix(
+        &mut context,
+        extend_lookup_table(
+            lookup_table_address,
+            extender.pubkey(),
+            Some(payer_address),
+            vec![new_address, initialized_table.addresses[0]],
+        ),
+        &extender,
+        lookup_table_address,
+    )
+    .await;
+    assert_eq!(unchanged_account, table_account);
+}
+
+#[tokio::test]
+async fn test_extend_authority_mode_lookup_table_with_other_signer() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let initialized_table = new_open_lookup_table(
+        Some(authority.pubkey()),
+        10,
+        LookupTableExtendMode::Authority,
+    );
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(&mut context, lookup_table_address, initialized_table).await;
+
+    let extender = Keypair::new();
+    let ix = extend_lookup_table(
+        lookup_table_address,
+        extender.pubkey(),
+        Some(context.payer.pubkey()),
+        vec![Pubkey::new_unique()],
+    );
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&extender),
+        // [Core BPF]: TODO: Should be `ProgramError::IncorrectAuthority`
+        // See https://github.com/solana-labs/solana/pull/35113
+        // InstructionError::IncorrectAuthority,
+        InstructionError::Custom(0),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_extend_frozen_open_lookup_table() {
+    let mut context = setup_test_context().await;
+
+    let initialized_table = new_open_lookup_table(None, 10, LookupTableExtendMode::Open);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(&mut context, lookup_table_address, initialized_table).await;
+
+    let extender = Keypair::new();
+    let ix = extend_lookup_table(
+        lookup_table_address,
+        extender.pubkey(),
+        Some(context.payer.pubkey()),
+        vec![Pubkey::new_unique()],
+    );
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&extender),
+        // [Core BPF]: TODO: Should be `ProgramError::Immutable`
+        // See https://github.com/solana-labs/solana/pull/35113
+        // InstructionError::Immutable,
+        InstructionError::Custom(0),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_set_lookup_table_extend_mode_with_wrong_authority() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let wrong_authority = Keypair::new();
+    let initialized_table =
+        new_open_lookup_table(Some(authority.pubkey()), 10, LookupTableExtendMode::Open);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(&mut context, lookup_table_address, initialized_table).await;
+
+    let ix = set_lookup_table_extend_mode(
+        lookup_table_address,
+        wrong_authority.pubkey(),
+        LookupTableExtendMode::Authority,
+    );
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&wrong_authority),
+        // [Core BPF]: TODO: Should be `ProgramError::IncorrectAuthority`
+        // See https://github.com/solana-labs/solana/pull/35113
+        // InstructionError::IncorrectAuthority,
+        InstructionError::Custom(0),
+    )
+    .await;
+}
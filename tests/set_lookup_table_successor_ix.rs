@@ -0,0 +1,284 @@
+#![cfg(feature = "test-sbf")]
+
+use {
+    common::{
+        add_lookup_table_account, add_lookup_table_account_with_settings, assert_ix_error,
+        get_lookup_table_settings, new_address_lookup_table, process_ix, process_ix_with_signers,
+        setup_test_context,
+    },
+    solana_program_test::*,
+    solana_programs_address_lookup_table::{
+        error::AddressLookupError,
+        instruction::{
+            close_lookup_table, derive_lookup_table_settings_address,
+            freeze_lookup_table_with_successor, set_lookup_table_successor,
+        },
+        resolver::resolve_lookup_table_account,
+        state::{AddressLookupTable, LookupTableSettings, LOOKUP_TABLE_META_SIZE},
+    },
+    solana_sdk::{
+        clock::{Clock, Slot},
+        instruction::{AccountMeta, InstructionError},
+        pubkey::Pubkey,
+        pubkey::PUBKEY_BYTES,
+        signature::{Keypair, Signer},
+        slot_hashes::SlotHashes,
+        sysvar::Sysvar,
+    },
+    std::collections::HashMap,
+};
+
+mod common;
+
+#[tokio::test]
+async fn test_set_deactivated_lookup_table_successor() {
+    let mut context = setup_test_context().await;
+    let payer_address = context.payer.pubkey();
+
+    let authority = Keypair::new();
+    let mut initialized_table = new_address_lookup_table(Some(authority.pubkey()), 10);
+    initialized_table.meta.deactivation_slot = 0;
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(
+        &mut context,
+        lookup_table_address,
+        initialized_table.clone(),
+    )
+    .await;
+
+    let successor = Pubkey::new_unique();
+    let table_account = process_ix(
+        &mut context,
+        set_lookup_table_successor(
+            lookup_table_address,
+            authority.pubkey(),
+            Some(payer_address),
+            successor,
+        ),
+        &authority,
+        lookup_table_address,
+    )
+    .await;
+
+    // The successor is stored in a new settings account, so the table isn't
+    // resized
+    assert_eq!(
+        table_account.data.len(),
+        LOOKUP_TABLE_META_SIZE + 10 * PUBKEY_BYTES
+    );
+    let lookup_table = AddressLookupTable::deserialize(&table_account.data).unwrap();
+    assert!(lookup_table.meta.has_settings());
+    assert_eq!(lookup_table.addresses, initialized_table.addresses);
+    assert_eq!(
+        get_lookup_table_settings(&mut context, lookup_table_address)
+            .await
+            .successor,
+        Some(successor)
+    );
+}
+
+#[tokio::test]
+async fn test_set_frozen_until_lookup_table_successor() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 10);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account_with_settings(
+        &mut context,
+        lookup_table_address,
+        initialized_table,
+        LookupTableSettings {
+            frozen_until: Some(Slot::MAX),
+            ..LookupTableSettings::new(lookup_table_address)
+        },
+    )
+    .await;
+
+    // Tables with a settings account don't need a payer
+    let successor = Pubkey::new_unique();
+    process_ix(
+        &mut context,
+        set_lookup_table_successor(lookup_table_address, authority.pubkey(), None, successor),
+        &authority,
+        lookup_table_address,
+    )
+    .await;
+
+    assert_eq!(
+        get_lookup_table_settings(&mut context, lookup_table_address).await,
+        LookupTableSettings {
+            frozen_until: Some(Slot::MAX),
+            successor: Some(successor),
+            ..LookupTableSettings::new(lookup_table_address)
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_set_active_lookup_table_successor() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 10);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(&mut context, lookup_table_address, initialized_table).await;
+
+    let ix = set_lookup_table_successor(
+        lookup_table_address,
+        authority.pubkey(),
+        Some(context.payer.pubkey()),
+        Pubkey::new_unique(),
+    );
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        InstructionError::InvalidArgument,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_set_lookup_table_successor_to_itself() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let mut initialized_table = new_address_lookup_table(Some(authority.pubkey()), 10);
+    initialized_table.meta.deactivation_slot = 0;
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(&mut context, lookup_table_address, initialized_table).await;
+
+    let ix = set_lookup_table_successor(
+        lookup_table_address,
+        authority.pubkey(),
+        Some(context.payer.pubkey()),
+        lookup_table_address,
+    );
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        InstructionError::Custom(AddressLookupError::InvalidSuccessor as u32),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_set_frozen_lookup_table_successor() {
+    let mut context = setup_test_context().await;
+
+    let authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(None, 10);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(&mut context, lookup_table_address, initialized_table).await;
+
+    let ix = set_lookup_table_successor(
+        lookup_table_address,
+        authority.pubkey(),
+        Some(context.payer.pubkey()),
+        Pubkey::new_unique(),
+    );
+
+    assert_ix_error(
+        &mut context,
+        ix,
+        Some(&authority),
+        // [Core BPF]: TODO: Should be `ProgramError::Immutable`
+        // See https://github.com/solana-labs/solana/pull/35113
+        // InstructionError::Immutable,
+        InstructionError::Custom(0),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_freeze_lookup_table_with_successor() {
+    let mut context = setup_test_context().await;
+    let payer_address = context.payer.pubkey();
+
+    let authority = Keypair::new();
+    let initialized_table = new_address_lookup_table(Some(authority.pubkey()), 10);
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account(&mut context, lookup_table_address, initialized_table).await;
+
+    let successor = Pubkey::new_unique();
+    let table_account = process_ix(
+        &mut context,
+        freeze_lookup_table_with_successor(
+            lookup_table_address,
+            authority.pubkey(),
+            Some(payer_address),
+            successor,
+        ),
+        &authority,
+        lookup_table_address,
+    )
+    .await;
+
+    let lookup_table = AddressLookupTable::deserialize(&table_account.data).unwrap();
+    assert_eq!(lookup_table.meta.authority, None);
+    assert!(lookup_table.meta.has_settings());
+    assert_eq!(
+        get_lookup_table_settings(&mut context, lookup_table_address)
+            .await
+            .successor,
+        Some(successor)
+    );
+}
+
+#[tokio::test]
+async fn test_resolve_lookup_table_successor_after_close() {
+    let mut context = setup_test_context().await;
+    context
+        .warp_to_slot(SlotHashes::size_of() as u64 + 1)
+        .unwrap();
+
+    let authority = Keypair::new();
+    let successor = Pubkey::new_unique();
+    let successor_table = new_address_lookup_table(Some(authority.pubkey()), 10);
+    add_lookup_table_account(&mut context, successor, successor_table.clone()).await;
+
+    let mut retired_table = new_address_lookup_table(Some(authority.pubkey()), 10);
+    retired_table.meta.deactivation_slot = 0;
+    let lookup_table_address = Pubkey::new_unique();
+    add_lookup_table_account_with_settings(
+        &mut context,
+        lookup_table_address,
+        retired_table,
+        LookupTableSettings {
+            successor: Some(successor),
+            ..LookupTableSettings::new(lookup_table_address)
+        },
+    )
+    .await;
+
+    let (settings_address, _) = derive_lookup_table_settings_address(&lookup_table_address);
+    let mut ix = close_lookup_table(
+        lookup_table_address,
+        authority.pubkey(),
+        context.payer.pubkey(),
+    );
+    ix.accounts.push(AccountMeta::new(settings_address, false));
+    process_ix_with_signers(&mut context, ix, &[&authority]).await;
+
+    // The settings account is kept, since it records a successor
+    let mut accounts = HashMap::new();
+    for address in [lookup_table_address, settings_address, successor] {
+        if let Some(account) = context.banks_client.get_account(address).await.unwrap() {
+            accounts.insert(address, (account.owner, account.data));
+        }
+    }
+    assert!(!accounts.contains_key(&lookup_table_address));
+    assert!(accounts.contains_key(&settings_address));
+
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    let resolved = resolve_lookup_table_account(&lookup_table_address, clock.slot, |address| {
+        accounts.get(address).cloned()
+    })
+    .unwrap();
+    assert_eq!(resolved.key, successor);
+    assert_eq!(resolved.addresses, successor_table.addresses.to_vec());
+}